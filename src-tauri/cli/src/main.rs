@@ -0,0 +1,664 @@
+//! Headless LiveSaver: runs copify and mover from scripts, cron or
+//! machines without a display. Shares history and presets with the app.
+
+use chrono::Local;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use livesaver_core::archive::{export_archive, import_archive, ArchiveFormat};
+use livesaver_core::copify::{copify_job, copify_set, default_backup_retention, BackupLocation, CopifySettings};
+use livesaver_core::error::Error;
+use livesaver_core::history::{record_job, History, JobOutcome, JobSettings};
+use livesaver_core::jobs::{JobKind, Jobs};
+use livesaver_core::mover::{default_target_template, mover_job, MoverSettings};
+use livesaver_core::presets::Presets;
+use livesaver_core::watch::{default_settle_ms, watched_folder, ProjectWatcher};
+use livesaver_core::share::{share_project, ShareSettings};
+use livesaver_core::report::{find_report, render, write_report, ProjectStatus, ReportFormat, RunReport};
+use livesaver_core::utils::{
+    app_data_dir, find_by_extension, is_backup_folder, read_set_info, ConflictPolicy, CopyOptions, DiscoveryOptions,
+    Incremental, JobEvent, JobEventKind, Reporter, SampleStatus, ScanError, ALS,
+};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(name = "livesaver-cli", version, about = "Collect and move Ableton Live projects")]
+struct Cli {
+    /// Folder holding history and presets, defaults to the app data folder
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+
+    /// Only print errors and the summary
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Collect the samples of every set below a folder into its project
+    Copify {
+        folder: Option<String>,
+        #[command(flatten)]
+        options: JobOptions,
+    },
+    /// Move or copy project folders to a target and collect their samples
+    Move {
+        folder: Option<String>,
+        target: Option<String>,
+        /// Move the project folders instead of copying them
+        #[arg(long)]
+        move_files: bool,
+        /// What to do when a project folder already exists in the target
+        #[arg(long, value_enum)]
+        on_conflict: Option<OnConflict>,
+        /// Path of each project in the target, from {project}, {set}, {year}, {month}, {day}, {tempo} and {live}
+        #[arg(long)]
+        target_template: Option<String>,
+        /// Keep the folders between the scanned folder and each project under the target
+        #[arg(long)]
+        preserve_hierarchy: bool,
+        #[command(flatten)]
+        options: JobOptions,
+    },
+    /// Collect sets as soon as Live saved them, until interrupted
+    Watch {
+        #[arg(required = true)]
+        folders: Vec<String>,
+        /// Time without changes before a saved set is collected
+        #[arg(long, default_value_t = default_settle_ms())]
+        settle_ms: u64,
+        #[command(flatten)]
+        options: JobOptions,
+    },
+    /// Collect the samples of a project and pack it into one archive with a manifest
+    Archive {
+        project: String,
+        /// Archive file, defaults to the project folder name next to it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = Packing::Zip)]
+        format: Packing,
+        /// Pack the project as it is, without collecting its samples first
+        #[arg(long)]
+        no_collect: bool,
+        #[command(flatten)]
+        options: JobOptions,
+    },
+    /// Collect a project and copy it without backups into a package for collaborators
+    Share {
+        project: String,
+        /// Folder the package is created in
+        output: PathBuf,
+        /// Leave out frozen tracks
+        #[arg(long)]
+        drop_freeze_files: bool,
+        /// Add a README listing plugins, Live version, tempo and missing media
+        #[arg(long)]
+        readme: bool,
+        /// Also pack the package into an archive
+        #[arg(long, value_enum)]
+        archive: Option<Packing>,
+        /// Share the project as it is, without collecting its samples first
+        #[arg(long)]
+        no_collect: bool,
+        #[command(flatten)]
+        options: JobOptions,
+    },
+    /// Unpack a project archive and check it against its manifest
+    Import {
+        archive: PathBuf,
+        /// Folder the project folder is created in
+        destination: PathBuf,
+    },
+    /// List the sets below a folder
+    Scan {
+        folder: String,
+        #[command(flatten)]
+        discovery: DiscoveryArgs,
+    },
+    /// Show the Live version and samples referenced by a set
+    Info {
+        set: PathBuf,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print or export the report of a past job
+    Report {
+        job_id: String,
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// List past jobs, newest first
+    Jobs {
+        /// Only jobs that touched this folder or set
+        project: Option<String>,
+    },
+}
+
+#[derive(Args)]
+struct JobOptions {
+    /// Start from a saved preset, other options override it
+    #[arg(long)]
+    preset: Option<String>,
+    /// Include Serum noise samples
+    #[arg(long)]
+    serum_noises: bool,
+    /// Move samples instead of copying them
+    #[arg(long)]
+    move_samples: bool,
+    /// Back up each set before modifying it
+    #[arg(long)]
+    backup: bool,
+    /// Write backups to this folder instead of next to the set
+    #[arg(long)]
+    backup_dir: Option<String>,
+    /// Backups kept per set, 0 keeps all
+    #[arg(long)]
+    backup_retention: Option<usize>,
+    /// Skip sets matching this gitignore-style pattern, can be repeated
+    #[arg(long = "exclude")]
+    exclude_files: Vec<String>,
+    /// Projects processed at the same time, 0 uses all cores
+    #[arg(short, long)]
+    jobs: Option<usize>,
+    /// Do not keep timestamps, permissions and extended attributes of copied files
+    #[arg(long)]
+    no_preserve_metadata: bool,
+    /// Compare every copy with its source by checksum and copy again when they differ
+    #[arg(long)]
+    verify: bool,
+    /// Skip files already up to date at the destination, so a run can be repeated or resumed
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "size-and-time")]
+    incremental: Option<UpToDate>,
+    #[command(flatten)]
+    discovery: DiscoveryArgs,
+    /// Also write the report to this file
+    #[arg(long)]
+    report: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    report_format: Format,
+}
+
+#[derive(Args)]
+struct DiscoveryArgs {
+    /// Levels of folders to scan, 1 only finds sets directly in the folder
+    #[arg(long)]
+    max_depth: Option<usize>,
+    /// Scan symlinked folders too
+    #[arg(long)]
+    follow_symlinks: bool,
+    /// Scan hidden and system folders
+    #[arg(long)]
+    include_hidden: bool,
+    /// Scan `Ableton Project Info` folders
+    #[arg(long)]
+    include_project_info: bool,
+}
+
+impl DiscoveryArgs {
+    fn apply(&self, options: &mut DiscoveryOptions) {
+        options.max_depth = self.max_depth.or(options.max_depth);
+        options.follow_symlinks |= self.follow_symlinks;
+        options.skip_hidden &= !self.include_hidden;
+        options.skip_project_info &= !self.include_project_info;
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Csv,
+    Html,
+}
+
+impl From<Format> for ReportFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Json => ReportFormat::Json,
+            Format::Csv => ReportFormat::Csv,
+            Format::Html => ReportFormat::Html,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OnConflict {
+    Skip,
+    Rename,
+    Merge,
+    Overwrite,
+    Fail,
+}
+
+impl From<OnConflict> for ConflictPolicy {
+    fn from(policy: OnConflict) -> Self {
+        match policy {
+            OnConflict::Skip => ConflictPolicy::Skip,
+            OnConflict::Rename => ConflictPolicy::Rename,
+            OnConflict::Merge => ConflictPolicy::Merge,
+            OnConflict::Overwrite => ConflictPolicy::Overwrite,
+            OnConflict::Fail => ConflictPolicy::Fail,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum UpToDate {
+    SizeAndTime,
+    Checksum,
+}
+
+impl From<UpToDate> for Incremental {
+    fn from(check: UpToDate) -> Self {
+        match check {
+            UpToDate::SizeAndTime => Incremental::SizeAndTime,
+            UpToDate::Checksum => Incremental::Checksum,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Packing {
+    Zip,
+    TarZst,
+}
+
+impl From<Packing> for ArchiveFormat {
+    fn from(packing: Packing) -> Self {
+        match packing {
+            Packing::Zip => ArchiveFormat::Zip,
+            Packing::TarZst => ArchiveFormat::TarZst,
+        }
+    }
+}
+
+/// Apply the command line options on top of copify or mover settings,
+/// which share these fields
+macro_rules! apply_options {
+    ($settings:ident, $options:ident) => {
+        $settings.serum_noises |= $options.serum_noises;
+        $settings.move_samples |= $options.move_samples;
+        $settings.create_backup |= $options.backup;
+        $settings.exclude_files.extend($options.exclude_files.iter().cloned());
+        if let Some(dir) = &$options.backup_dir {
+            $settings.backup_location = BackupLocation::Central(dir.clone());
+        }
+        $settings.backup_retention = $options.backup_retention.unwrap_or($settings.backup_retention);
+        $settings.concurrency = $options.jobs.unwrap_or($settings.concurrency);
+        $options.discovery.apply(&mut $settings.discovery);
+        $settings.copy_options.preserve_metadata &= !$options.no_preserve_metadata;
+        $settings.copy_options.verify |= $options.verify;
+        if let Some(check) = $options.incremental {
+            $settings.copy_options.incremental = check.into();
+        }
+    };
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<ExitCode, Error> {
+    let data_dir = cli
+        .data_dir
+        .or_else(app_data_dir)
+        .ok_or_else(|| Error::Generic("Could not find the app data folder, pass --data-dir".to_string()))?;
+    let history = History::open(&data_dir);
+    let presets = Presets::open(&data_dir);
+
+    match cli.command {
+        Command::Copify { folder, options } => {
+            let settings = copify_settings(&presets, folder, &options)?;
+            run_job(&history, &options, cli.quiet, JobSettings::Copify(settings))
+        }
+        Command::Move { folder, target, move_files, on_conflict, target_template, preserve_hierarchy, options } => {
+            let mut settings = match &options.preset {
+                Some(name) => match presets.get(name)?.settings {
+                    JobSettings::Mover(settings) => settings,
+                    _ => return Err(Error::Generic(format!("Preset {} is not a mover preset", name))),
+                },
+                None => MoverSettings {
+                    move_project_files: false,
+                    serum_noises: false,
+                    move_samples: false,
+                    create_backup: false,
+                    folder: String::new(),
+                    target: String::new(),
+                    target_template: default_target_template(),
+                    preserve_hierarchy: false,
+                    exclude_files: Vec::new(),
+                    backup_location: BackupLocation::Project,
+                    backup_retention: default_backup_retention(),
+                    concurrency: 0,
+                    conflict_policy: ConflictPolicy::default(),
+                    discovery: DiscoveryOptions::default(),
+                    copy_options: CopyOptions::default(),
+                },
+            };
+            if let Some(folder) = folder {
+                settings.folder = folder;
+            }
+            if let Some(target) = target {
+                settings.target = target;
+            }
+            settings.move_project_files |= move_files;
+            settings.preserve_hierarchy |= preserve_hierarchy;
+            if let Some(policy) = on_conflict {
+                settings.conflict_policy = policy.into();
+            }
+            if let Some(template) = target_template {
+                settings.target_template = template;
+            }
+            apply_options!(settings, options);
+
+            if settings.folder.is_empty() || settings.target.is_empty() {
+                return Err(Error::Generic("Both a folder and a target are required".to_string()));
+            }
+
+            run_job(&history, &options, cli.quiet, JobSettings::Mover(settings))
+        }
+        Command::Watch { folders, settle_ms, options } => {
+            let settings = copify_settings(&presets, Some(folders[0].clone()), &options)?;
+            let folders: Vec<PathBuf> = folders.iter().map(PathBuf::from).collect();
+            let quiet = cli.quiet;
+            let jobs = Jobs::default();
+
+            let watched = folders.clone();
+            let _watcher = ProjectWatcher::start(&folders, Duration::from_millis(settle_ms), move |set| {
+                let job = jobs.start();
+                let started_at = Local::now();
+                let mut settings = settings.clone();
+                settings.folder = watched_folder(&watched, set).to_string_lossy().to_string();
+
+                let reporter = print_reporter(&job.id, quiet);
+                let result = copify_set(&job.id, &job.token, &settings, set, &reporter);
+                record_job(&history, &job.id, JobSettings::Copify(settings), started_at, &result);
+                match &result {
+                    Ok(report) => print_summary(report),
+                    Err(e) => eprintln!("error: {}", e),
+                }
+            })?;
+
+            if !quiet {
+                eprintln!("Watching {} folders, press Ctrl+C to stop", folders.len());
+            }
+            loop {
+                thread::park();
+            }
+        }
+        Command::Archive { project, output, format, no_collect, options } => {
+            if !no_collect {
+                let settings = copify_settings(&presets, Some(project.clone()), &options)?.for_project(&project);
+                let code = run_job(&history, &options, cli.quiet, JobSettings::Copify(settings))?;
+                if code != ExitCode::SUCCESS {
+                    return Ok(code);
+                }
+            }
+
+            let format = ArchiveFormat::from(format);
+            let output = output.unwrap_or_else(|| {
+                PathBuf::from(format!("{}.{}", project.trim_end_matches(['/', '\\']), format.extension()))
+            });
+
+            let jobs = Jobs::default();
+            let job = jobs.start();
+            let reporter = print_reporter(&job.id, cli.quiet);
+            let manifest = export_archive(Path::new(&project), &output, format, &job.token, &reporter)?;
+            println!(
+                "Packed {} files of {} sets into {}",
+                manifest.files.len(),
+                manifest.sets.len(),
+                output.display()
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Share { project, output, drop_freeze_files, readme, archive, no_collect, options } => {
+            if !no_collect {
+                let settings = copify_settings(&presets, Some(project.clone()), &options)?.for_project(&project);
+                let code = run_job(&history, &options, cli.quiet, JobSettings::Copify(settings))?;
+                if code != ExitCode::SUCCESS {
+                    return Ok(code);
+                }
+            }
+
+            let settings = ShareSettings {
+                drop_freeze_files,
+                readme,
+                archive: archive.map(ArchiveFormat::from),
+            };
+            let jobs = Jobs::default();
+            let job = jobs.start();
+            let reporter = print_reporter(&job.id, cli.quiet);
+            let package = share_project(Path::new(&project), &output, &settings, &job.token, &reporter)?;
+
+            println!(
+                "Shared {} files of {} sets in {}",
+                package.files,
+                package.sets.len(),
+                package.archive.as_ref().unwrap_or(&package.folder).display()
+            );
+            for set in &package.sets {
+                for sample in set.missing_samples() {
+                    eprintln!("warning: {} is missing {}", set.path.display(), sample.path);
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Import { archive, destination } => {
+            let jobs = Jobs::default();
+            let job = jobs.start();
+            let reporter = print_reporter(&job.id, cli.quiet);
+            let imported = import_archive(&archive, &destination, &reporter)?;
+
+            for path in &imported.missing {
+                eprintln!("missing:    {}", path);
+            }
+            for path in &imported.mismatched {
+                eprintln!("mismatched: {}", path);
+            }
+            println!(
+                "Unpacked {} files into {}: {} missing, {} mismatched",
+                imported.manifest.files.len(),
+                imported.project.display(),
+                imported.missing.len(),
+                imported.mismatched.len()
+            );
+            Ok(if imported.is_intact() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+        }
+        Command::Scan { folder, discovery } => {
+            let mut options = DiscoveryOptions::default();
+            discovery.apply(&mut options);
+
+            let discovery = find_by_extension(&folder, ALS, &options);
+            for file in &discovery.files {
+                println!("{}", file.display());
+            }
+            print_scan_errors(&discovery.errors);
+            if !cli.quiet {
+                eprintln!("{} sets found", discovery.files.len());
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Info { set, json } => {
+            let info = read_set_info(&set)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&info).map_err(|e| Error::Generic(e.to_string()))?);
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            println!("Set:      {}", info.path.display());
+            println!("Live:     {}", info.live_version.as_deref().unwrap_or("unknown"));
+            if let Some(tempo) = info.tempo {
+                println!("Tempo:    {} BPM", tempo);
+            }
+            println!("Size:     {} bytes", info.size);
+            if let Some(modified) = info.modified {
+                println!("Modified: {}", modified.format("%Y-%m-%d %H:%M:%S"));
+            }
+            println!("Backup:   {}", if is_backup_folder(&info.path) { "yes" } else { "no" });
+            println!(
+                "Samples:  {} ({} missing)",
+                info.samples.len(),
+                info.missing_samples().count()
+            );
+            for sample in &info.samples {
+                println!("  {} {}", if sample.exists { " " } else { "!" }, sample.path);
+            }
+            println!("Plugins:  {}", info.plugins.len());
+            for plugin in &info.plugins {
+                println!("    {}", plugin);
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Report { job_id, format, output } => {
+            let report = find_report(&history, &job_id)?;
+
+            match output {
+                Some(path) => write_report(&report, format.into(), &path)?,
+                None => println!("{}", render(&report, format.into())?.trim_end()),
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Jobs { project } => {
+            for job in history
+                .list()?
+                .iter()
+                .filter(|job| project.as_ref().is_none_or(|p| job.touches(Path::new(p))))
+            {
+                let outcome = match &job.outcome {
+                    JobOutcome::Completed => "completed".to_string(),
+                    JobOutcome::Cancelled => "cancelled".to_string(),
+                    JobOutcome::Failed { error } => format!("failed: {}", error),
+                };
+                println!(
+                    "{}  {}  {:<6}  {}  {}",
+                    job.job_id,
+                    job.started_at.format("%Y-%m-%d %H:%M:%S"),
+                    job.settings.kind().label(),
+                    job.settings.folder(),
+                    outcome
+                );
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// Run a job, record it in the history and print a summary.
+/// Fails the process when any project failed.
+fn run_job(history: &History, options: &JobOptions, quiet: bool, settings: JobSettings) -> Result<ExitCode, Error> {
+    let jobs = Jobs::default();
+    let job = jobs.start();
+    let started_at = Local::now();
+    let reporter = print_reporter(&job.id, quiet);
+
+    let result = match &settings {
+        JobSettings::Copify(copify_settings) => copify_job(&job.id, &job.token, copify_settings, &reporter),
+        JobSettings::Mover(mover_settings) => mover_job(&job.id, &job.token, mover_settings, &reporter),
+    };
+    record_job(history, &job.id, settings, started_at, &result);
+    let report = result?;
+
+    if let Some(path) = &options.report {
+        write_report(&report, options.report_format.into(), path)?;
+    }
+    print_summary(&report);
+
+    Ok(if report.count(ProjectStatus::Failed) > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Copify settings from the preset and command line options
+fn copify_settings(presets: &Presets, folder: Option<String>, options: &JobOptions) -> Result<CopifySettings, Error> {
+    let mut settings = match &options.preset {
+        Some(name) => match presets.get(name)?.settings {
+            JobSettings::Copify(settings) => settings,
+            _ => return Err(Error::Generic(format!("Preset {} is not a copify preset", name))),
+        },
+        None => CopifySettings {
+            serum_noises: false,
+            move_samples: false,
+            create_backup: false,
+            folder: String::new(),
+            exclude_files: Vec::new(),
+            backup_location: BackupLocation::Project,
+            backup_retention: default_backup_retention(),
+            concurrency: 0,
+            discovery: DiscoveryOptions::default(),
+            copy_options: CopyOptions::default(),
+        },
+    };
+    if let Some(folder) = folder {
+        settings.folder = folder;
+    }
+    apply_options!(settings, options);
+
+    if settings.folder.is_empty() {
+        return Err(Error::Generic("No folder given".to_string()));
+    }
+
+    Ok(settings)
+}
+
+/// Reporter that prints the outcome of every project to stderr
+fn print_reporter(job_id: &str, quiet: bool) -> Reporter {
+    Reporter::new(job_id, move |event: JobEvent| match event.kind {
+        JobEventKind::Project(progress) => {
+            if progress.is_error {
+                eprintln!("[{:>3}%] failed  {}: {}", progress.progress, progress.file_name, progress.error_msg);
+            } else if !quiet {
+                let status = if progress.is_skipped { "skipped" } else { "done   " };
+                eprintln!("[{:>3}%] {} {}", progress.progress, status, progress.file_name);
+            }
+        }
+        JobEventKind::Sample { file_name, sample, status: SampleStatus::Mismatched } => {
+            eprintln!("warning: copy of {} for {} did not match, the set keeps using the original", sample, file_name);
+        }
+        _ => {}
+    })
+}
+
+fn print_scan_errors(errors: &[ScanError]) {
+    for error in errors {
+        eprintln!("warning: could not scan: {}", error.message);
+    }
+}
+
+fn print_summary(report: &RunReport) {
+    print_scan_errors(&report.scan_errors);
+    let verb = match report.kind {
+        JobKind::Copify => "Collected",
+        JobKind::Mover => "Moved",
+    };
+    println!(
+        "{} {} projects in {} ms: {} processed, {} skipped, {} failed, {} bytes copied (job {})",
+        verb,
+        report.projects.len(),
+        report.duration_ms,
+        report.count(ProjectStatus::Processed),
+        report.count(ProjectStatus::Skipped),
+        report.count(ProjectStatus::Failed),
+        report.bytes_copied(),
+        report.job_id
+    );
+}
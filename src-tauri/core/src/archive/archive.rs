@@ -0,0 +1,337 @@
+use crate::archive::models::{ArchiveFormat, ImportedArchive, Manifest, ManifestFile, ManifestSet};
+use crate::jobs::CancelToken;
+use crate::prelude::*;
+use crate::utils::{file_hash, is_backup_folder, read_set_info, Phase, Reporter, ALS, MANIFEST_FILE, MANIFEST_VERSION};
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone, Timelike};
+use filetime::FileTime;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Pack a project folder into a single archive, with a manifest of its
+/// sets, samples, plugins and the checksum of every file.
+///
+/// Returns `Error::Cancelled` before the next file once `cancel` is set.
+/// An incomplete archive is removed again.
+pub fn export_archive(
+    project: &Path,
+    output: &Path,
+    format: ArchiveFormat,
+    cancel: &CancelToken,
+    reporter: &Reporter,
+) -> Result<Manifest> {
+    let project = project.canonicalize().on(Operation::Read, project)?;
+    let manifest = build_manifest(&project, reporter)?;
+
+    reporter.phase(Phase::Compressing, Some(output));
+    reporter.add_total(manifest.files.iter().map(|f| f.size).sum());
+
+    let written = match format {
+        ArchiveFormat::Zip => write_zip(&project, output, &manifest, cancel, reporter),
+        ArchiveFormat::TarZst => write_tar_zst(&project, output, &manifest, cancel, reporter),
+    };
+
+    if let Err(e) = written {
+        let _ = fs::remove_file(output);
+        return Err(e);
+    }
+
+    Ok(manifest)
+}
+
+/// Describe a project folder, reading every set and checksumming every file
+pub fn build_manifest(project: &Path, reporter: &Reporter) -> Result<Manifest> {
+    let name = project
+        .file_name()
+        .ok_or(Error::Archive(format!("{} is not a project folder", project.display())))?
+        .to_string_lossy()
+        .into_owned();
+
+    reporter.phase(Phase::Scanning, Some(project));
+
+    let mut sets = Vec::new();
+    let mut files = Vec::new();
+
+    for entry in WalkDir::new(project).sort_by_file_name() {
+        let entry = entry.map_err(|e| Error::Archive(format!("Could not read {}: {}", project.display(), e)))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path();
+        let relative = slash_path(path.strip_prefix(project).unwrap_or(path));
+
+        if path.extension().is_some_and(|ext| ext == ALS) && !is_backup_folder(path) {
+            let info = read_set_info(path)?;
+            let (found, missing): (Vec<_>, Vec<_>) = info.samples.iter().partition(|s| s.exists);
+            sets.push(ManifestSet {
+                path: relative.clone(),
+                live_version: info.live_version.clone(),
+                tempo: info.tempo,
+                plugins: info.plugins.clone(),
+                samples: found.iter().map(|s| sample_name(project, &s.path)).collect(),
+                missing_samples: missing.iter().map(|s| s.path.clone()).collect(),
+            });
+        }
+
+        files.push(ManifestFile {
+            path: relative,
+            size: entry.metadata().map(|m| m.len()).unwrap_or_default(),
+            checksum: file_hash(path).on(Operation::Read, path)?.to_hex().to_string(),
+        });
+    }
+
+    Ok(Manifest {
+        version: MANIFEST_VERSION,
+        project: name,
+        created: Local::now(),
+        sets,
+        files,
+    })
+}
+
+/// Unpack a project archive into `destination` and check every file
+/// against its manifest. The project folder must not exist yet.
+pub fn import_archive(archive: &Path, destination: &Path, reporter: &Reporter) -> Result<ImportedArchive> {
+    let format = ArchiveFormat::from_path(archive)
+        .ok_or(Error::Archive(format!("{} is not a .zip or .tar.zst archive", archive.display())))?;
+    let file = File::open(archive).on(Operation::Read, archive)?;
+
+    let manifest = match format {
+        ArchiveFormat::Zip => unpack_zip(file, archive, destination, reporter)?,
+        ArchiveFormat::TarZst => unpack_tar_zst(file, archive, destination, reporter)?,
+    };
+
+    let project = destination.join(&manifest.project);
+    reporter.phase(Phase::Verifying, Some(&project));
+
+    let mut missing = Vec::new();
+    let mut mismatched = Vec::new();
+
+    for file in &manifest.files {
+        let path = project.join(&file.path);
+        match fs::metadata(&path) {
+            Err(_) => missing.push(file.path.clone()),
+            Ok(metadata) if metadata.len() != file.size => mismatched.push(file.path.clone()),
+            Ok(_) => {
+                if file_hash(&path).on(Operation::Read, &path)?.to_hex().as_str() != file.checksum {
+                    mismatched.push(file.path.clone());
+                }
+            }
+        }
+    }
+
+    Ok(ImportedArchive {
+        project,
+        manifest,
+        missing,
+        mismatched,
+    })
+}
+
+fn write_zip(project: &Path, output: &Path, manifest: &Manifest, cancel: &CancelToken, reporter: &Reporter) -> Result<()> {
+    let file = File::create(output).on(Operation::Create, output)?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file(MANIFEST_FILE, options)?;
+    zip.write_all(&manifest_json(manifest)?).on(Operation::Compress, output)?;
+
+    for file in &manifest.files {
+        if cancel.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        let source = project.join(&file.path);
+        let name = format!("{}/{}", manifest.project, file.path);
+        let mut file_options = options.large_file(file.size >= u32::MAX as u64);
+        if let Some(modified) = fs::metadata(&source).and_then(|m| m.modified()).ok().and_then(zip_time) {
+            file_options = file_options.last_modified_time(modified);
+        }
+        zip.start_file(name, file_options)?;
+        let mut reader = File::open(&source).on(Operation::Read, &source)?;
+        io::copy(&mut reader, &mut zip).on(Operation::Compress, &source)?;
+        reporter.advance(file.size);
+    }
+
+    zip.finish()?.flush().on(Operation::Write, output)
+}
+
+fn write_tar_zst(project: &Path, output: &Path, manifest: &Manifest, cancel: &CancelToken, reporter: &Reporter) -> Result<()> {
+    let file = File::create(output).on(Operation::Create, output)?;
+    let encoder = zstd::Encoder::new(BufWriter::new(file), 0).on(Operation::Compress, output)?;
+    let mut tar = tar::Builder::new(encoder);
+
+    // First, so an import knows the project before unpacking anything
+    let json = manifest_json(manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created.timestamp().max(0) as u64);
+    header.set_cksum();
+    tar.append_data(&mut header, MANIFEST_FILE, json.as_slice()).on(Operation::Compress, output)?;
+
+    for file in &manifest.files {
+        if cancel.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        let source = project.join(&file.path);
+        let name = format!("{}/{}", manifest.project, file.path);
+        tar.append_path_with_name(&source, name).on(Operation::Compress, &source)?;
+        reporter.advance(file.size);
+    }
+
+    tar.into_inner()
+        .and_then(|encoder| encoder.finish())
+        .and_then(|mut writer| writer.flush())
+        .on(Operation::Compress, output)
+}
+
+fn unpack_zip(file: File, archive: &Path, destination: &Path, reporter: &Reporter) -> Result<Manifest> {
+    let mut zip = ZipArchive::new(BufReader::new(file))?;
+    let manifest = match zip.by_name(MANIFEST_FILE) {
+        Ok(entry) => read_manifest(entry)?,
+        Err(_) => return Err(no_manifest(archive)),
+    };
+    let project = create_project_folder(destination, &manifest)?;
+
+    reporter.phase(Phase::Extracting, Some(&project));
+    reporter.add_total(manifest.files.iter().map(|f| f.size).sum());
+
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index)?;
+
+        // Paths escaping the destination are never written
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+        if !relative.starts_with(&manifest.project) {
+            continue;
+        }
+
+        let path = destination.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&path).on(Operation::Create, &path)?;
+            continue;
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).on(Operation::Create, parent)?;
+        }
+        let mut writer = File::create(&path).on(Operation::Create, &path)?;
+        io::copy(&mut entry, &mut writer).on(Operation::Decompress, &path)?;
+        if let Some(modified) = entry.last_modified().and_then(system_time) {
+            let _ = filetime::set_file_mtime(&path, FileTime::from_system_time(modified));
+        }
+        reporter.advance(entry.size());
+    }
+
+    Ok(manifest)
+}
+
+fn unpack_tar_zst(file: File, archive: &Path, destination: &Path, reporter: &Reporter) -> Result<Manifest> {
+    let decoder = zstd::Decoder::new(file).on(Operation::Decompress, archive)?;
+    let mut tar = tar::Archive::new(decoder);
+    let mut entries = tar.entries().on(Operation::Decompress, archive)?;
+
+    let manifest = match entries.next() {
+        Some(Ok(entry)) if entry.path().is_ok_and(|path| path == Path::new(MANIFEST_FILE)) => read_manifest(entry)?,
+        _ => return Err(no_manifest(archive)),
+    };
+    let project = create_project_folder(destination, &manifest)?;
+
+    reporter.phase(Phase::Extracting, Some(&project));
+    reporter.add_total(manifest.files.iter().map(|f| f.size).sum());
+
+    for entry in entries {
+        let mut entry = entry.on(Operation::Decompress, archive)?;
+        let relative = entry.path().on(Operation::Decompress, archive)?.into_owned();
+        if !relative.starts_with(&manifest.project) {
+            continue;
+        }
+
+        // Skips paths that would escape the destination
+        let size = entry.size();
+        entry.unpack_in(destination).on(Operation::Decompress, &destination.join(&relative))?;
+        reporter.advance(size);
+    }
+
+    Ok(manifest)
+}
+
+/// The folder a project is unpacked to, never one that already exists
+fn create_project_folder(destination: &Path, manifest: &Manifest) -> Result<PathBuf> {
+    if manifest.version > MANIFEST_VERSION {
+        return Err(Error::Archive(format!(
+            "The archive was made by a newer version of LiveSaver (manifest version {})",
+            manifest.version
+        )));
+    }
+
+    let mut components = Path::new(&manifest.project).components();
+    if !matches!((components.next(), components.next()), (Some(Component::Normal(_)), None)) {
+        return Err(Error::Archive(format!("Invalid project name in manifest: {}", manifest.project)));
+    }
+
+    let project = destination.join(&manifest.project);
+    if project.exists() {
+        return Err(Error::io(Operation::Create, project, io::ErrorKind::AlreadyExists.into()));
+    }
+    fs::create_dir_all(&project).on(Operation::Create, &project)?;
+
+    Ok(project)
+}
+
+fn manifest_json(manifest: &Manifest) -> Result<Vec<u8>> {
+    serde_json::to_vec_pretty(manifest).map_err(|e| Error::Archive(format!("Could not write manifest: {}", e)))
+}
+
+fn read_manifest(reader: impl Read) -> Result<Manifest> {
+    serde_json::from_reader(reader).map_err(|e| Error::Archive(format!("Invalid manifest: {}", e)))
+}
+
+fn no_manifest(archive: &Path) -> Error {
+    Error::Archive(format!("{} is not a LiveSaver archive, it has no {}", archive.display(), MANIFEST_FILE))
+}
+
+/// A sample relative to the project folder when it was collected into it
+fn sample_name(project: &Path, sample: &str) -> String {
+    Path::new(sample)
+        .canonicalize()
+        .ok()
+        .and_then(|path| path.strip_prefix(project).map(slash_path).ok())
+        .unwrap_or_else(|| sample.to_string())
+}
+
+/// Zip stores local time in 2 second steps and nothing before 1980
+fn zip_time(time: SystemTime) -> Option<zip::DateTime> {
+    let local = DateTime::<Local>::from(time);
+    zip::DateTime::from_date_and_time(
+        u16::try_from(local.year()).ok()?,
+        local.month() as u8,
+        local.day() as u8,
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+    )
+    .ok()
+}
+
+fn system_time(time: zip::DateTime) -> Option<SystemTime> {
+    let local = NaiveDate::from_ymd_opt(time.year().into(), time.month().into(), time.day().into())?
+        .and_hms_opt(time.hour().into(), time.minute().into(), time.second().into())?;
+    Local.from_local_datetime(&local).earliest().map(SystemTime::from)
+}
+
+/// A relative path with `/` separators on every platform
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,5 @@
+pub(crate) mod archive;
+mod models;
+
+pub use archive::*;
+pub use models::*;
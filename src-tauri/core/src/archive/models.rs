@@ -0,0 +1,82 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    #[default]
+    Zip,
+    TarZst,
+}
+
+impl ArchiveFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::TarZst => "tar.zst",
+        }
+    }
+
+    /// The format of an archive by its file name
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+            Some(ArchiveFormat::TarZst)
+        } else {
+            None
+        }
+    }
+}
+
+/// Describes a project archive, stored next to the project folder in it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    // Layout of this manifest, newer archives are refused by older versions
+    pub version: u32,
+    // Name of the project folder in the archive
+    pub project: String,
+    pub created: DateTime<Local>,
+    pub sets: Vec<ManifestSet>,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManifestSet {
+    // Relative to the project folder, with `/` separators
+    pub path: String,
+    pub live_version: Option<String>,
+    pub tempo: Option<f64>,
+    pub plugins: Vec<String>,
+    // Relative to the project folder when collected, absolute otherwise
+    pub samples: Vec<String>,
+    pub missing_samples: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManifestFile {
+    // Relative to the project folder, with `/` separators
+    pub path: String,
+    pub size: u64,
+    // BLAKE3 of the content, hex encoded
+    pub checksum: String,
+}
+
+/// A project unpacked from an archive and checked against its manifest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImportedArchive {
+    pub project: PathBuf,
+    pub manifest: Manifest,
+    // Files of the manifest that were not in the archive
+    pub missing: Vec<String>,
+    // Files whose size or checksum differ from the manifest
+    pub mismatched: Vec<String>,
+}
+
+impl ImportedArchive {
+    pub fn is_intact(&self) -> bool {
+        self.missing.is_empty() && self.mismatched.is_empty()
+    }
+}
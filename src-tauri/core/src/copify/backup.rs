@@ -0,0 +1,237 @@
+use crate::copify::models::BackupLocation;
+use crate::error::{Error, IoContext, Operation};
+use crate::utils::*;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Timestamp format used in backup file names, e.g. `Song [2024-05-01 153012.123].als.bak`
+static BACKUP_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H%M%S%.3f";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created_at: DateTime<Local>,
+    pub size: u64,
+    // Single `.als.bak` written before backups were timestamped
+    pub is_legacy: bool,
+}
+
+/// Copy a set to a new timestamped backup and prune old backups
+/// so at most `retention` are kept. A `retention` of `0` keeps all.
+pub fn create_backup(set: &Path, location: &BackupLocation, retention: usize) -> Result<PathBuf, Error> {
+    if set.to_string_lossy().ends_with(ALS_BACKUP_EXTENSION) {
+        return Err(Error::CopifyFailed("Input file is not valid to backup".to_string()));
+    }
+
+    let dir = backup_dir(set, location)?;
+    fs::create_dir_all(&dir).on(Operation::Create, &dir)?;
+
+    let timestamp = Local::now().format(BACKUP_TIMESTAMP_FORMAT);
+    let backup = dir.join(format!("{} [{}]{}", set_stem(set)?, timestamp, ALS_BACKUP_EXTENSION));
+
+    // Never overwrite an existing backup, it may be the only pristine copy
+    if backup.exists() {
+        return Err(Error::CopifyFailed(format!("Backup already exists: {}", backup.display())));
+    }
+
+    fs::copy(set, &backup).between(Operation::Copy, set, &backup)?;
+
+    if retention > 0 {
+        let outdated = find_backups(set, location)?
+            .into_iter()
+            .filter(|b| !b.is_legacy)
+            .skip(retention);
+
+        for b in outdated {
+            fs::remove_file(&b.path).on(Operation::Remove, &b.path)?;
+        }
+    }
+
+    Ok(backup)
+}
+
+/// Find all backups of a set, newest first
+pub fn find_backups(set: &Path, location: &BackupLocation) -> Result<Vec<Backup>, Error> {
+    let dir = backup_dir(set, location)?;
+    let stem = set_stem(set)?;
+    let mut backups = Vec::new();
+
+    if !dir.is_dir() {
+        return Ok(backups);
+    }
+
+    let legacy_name = format!("{}{}", stem, ALS_BACKUP_EXTENSION);
+    let prefix = format!("{} [", stem);
+    let suffix = format!("]{}", ALS_BACKUP_EXTENSION);
+
+    for entry in fs::read_dir(&dir).on(Operation::List, &dir)? {
+        let entry = entry.on(Operation::List, &dir)?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let metadata = entry.metadata().on(Operation::Read, &entry.path())?;
+
+        let created_at = if name == legacy_name {
+            metadata
+                .modified()
+                .map(DateTime::<Local>::from)
+                .on(Operation::Read, &entry.path())?
+        } else if let Some(timestamp) = name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(&suffix))
+        {
+            match parse_timestamp(timestamp) {
+                Some(created_at) => created_at,
+                None => continue,
+            }
+        } else {
+            continue;
+        };
+
+        backups.push(Backup {
+            path: entry.path(),
+            created_at,
+            size: metadata.len(),
+            is_legacy: name == legacy_name,
+        });
+    }
+
+    backups.sort_by_key(|b| Reverse(b.created_at));
+
+    Ok(backups)
+}
+
+/// Replace a set with one of its backups
+pub fn restore(set: &Path, backup: &Path, location: &BackupLocation) -> Result<(), Error> {
+    let is_known = find_backups(set, location)?
+        .iter()
+        .any(|b| b.path == backup);
+
+    if !is_known {
+        return Err(Error::FileNotFound(format!(
+            "{} is not a backup of {}",
+            backup.display(),
+            set.display()
+        )));
+    }
+
+    // Copy next to the set first so a failed copy never leaves a truncated set behind
+    let mut temp = set.to_path_buf();
+    temp.set_extension("als.restore");
+    fs::copy(backup, &temp).between(Operation::Copy, backup, &temp)?;
+    fs::rename(&temp, set).between(Operation::Move, &temp, set)?;
+
+    Ok(())
+}
+
+fn backup_dir(set: &Path, location: &BackupLocation) -> Result<PathBuf, Error> {
+    let dir = set
+        .parent()
+        .ok_or_else(|| Error::FileNotFound("Invalid file path".to_string()))?;
+
+    match location {
+        BackupLocation::Project => Ok(dir.to_path_buf()),
+        BackupLocation::Central(folder) => {
+            // Flatten the set's folder into a single readable name
+            let flattened: String = dir
+                .to_string_lossy()
+                .chars()
+                .map(|c| if matches!(c, '/' | '\\' | ':') { '_' } else { c })
+                .collect();
+            Ok(Path::new(folder).join(flattened.trim_start_matches('_')))
+        }
+    }
+}
+
+fn set_stem(set: &Path) -> Result<String, Error> {
+    set.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .ok_or_else(|| Error::FileNotFound("Invalid file path".to_string()))
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Local>> {
+    let naive = NaiveDateTime::parse_from_str(value, BACKUP_TIMESTAMP_FORMAT).ok()?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Back up `set` once per content, each a few milliseconds apart so
+    /// every backup gets its own timestamp
+    fn backups(set: &Path, location: &BackupLocation, retention: usize, contents: &[&str]) -> Vec<PathBuf> {
+        contents
+            .iter()
+            .map(|content| {
+                thread::sleep(Duration::from_millis(5));
+                fs::write(set, content).unwrap();
+                create_backup(set, location, retention).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn prunes_the_oldest_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let set = dir.path().join("Song.als");
+        let legacy = dir.path().join("Song.als.bak");
+        fs::write(&legacy, "legacy").unwrap();
+
+        let created = backups(&set, &BackupLocation::Project, 2, &["1", "2", "3"]);
+        let kept = find_backups(&set, &BackupLocation::Project).unwrap();
+
+        let timestamped: Vec<_> = kept.iter().filter(|b| !b.is_legacy).map(|b| b.path.clone()).collect();
+        assert_eq!(timestamped, vec![created[2].clone(), created[1].clone()]);
+        assert!(!created[0].exists());
+        // The legacy backup is never pruned
+        assert!(legacy.exists());
+    }
+
+    #[test]
+    fn writes_backups_to_the_central_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("Song Project");
+        let central = dir.path().join("Backups");
+        fs::create_dir_all(&project).unwrap();
+        let set = project.join("Song.als");
+        let location = BackupLocation::Central(central.to_string_lossy().to_string());
+
+        let created = backups(&set, &location, 0, &["1"]);
+
+        assert!(created[0].starts_with(&central));
+        assert!(created[0].parent().unwrap().to_string_lossy().ends_with("Song Project"));
+        assert_eq!(fs::read_dir(&project).unwrap().count(), 1);
+        assert_eq!(find_backups(&set, &location).unwrap()[0].path, created[0]);
+        assert!(find_backups(&set, &BackupLocation::Project).unwrap().is_empty());
+    }
+
+    #[test]
+    fn restores_a_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let set = dir.path().join("Song.als");
+        let created = backups(&set, &BackupLocation::Project, 0, &["first", "second"]);
+        fs::write(&set, "broken").unwrap();
+
+        restore(&set, &created[0], &BackupLocation::Project).unwrap();
+
+        assert_eq!(fs::read_to_string(&set).unwrap(), "first");
+        assert!(created[0].exists());
+        assert!(!set.with_extension("als.restore").exists());
+    }
+
+    #[test]
+    fn only_restores_backups_of_the_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let set = dir.path().join("Song.als");
+        let other = dir.path().join("Other.als");
+        let created = backups(&other, &BackupLocation::Project, 0, &["other"]);
+        fs::write(&set, "song").unwrap();
+
+        assert!(restore(&set, &created[0], &BackupLocation::Project).is_err());
+        assert_eq!(fs::read_to_string(&set).unwrap(), "song");
+    }
+}
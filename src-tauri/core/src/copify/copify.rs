@@ -0,0 +1,203 @@
+use crate::copify::backup::create_backup;
+use crate::copify::models::{CopifySettings, SampleStats};
+use crate::copify::{referenced_bytes, update_sample_refs};
+use crate::utils::*;
+use crate::error::{Error, IoContext, Operation};
+use crate::jobs::{CancelToken, JobKind};
+use crate::report::{ProjectReport, ProjectStatus, RunReport};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Scan `settings.folder` and run copify on every project file found
+pub fn copify_job(
+    job_id: &str,
+    cancel: &CancelToken,
+    settings: &CopifySettings,
+    reporter: &Reporter,
+) -> Result<RunReport, Error> {
+    reporter.phase(Phase::Scanning, None);
+
+    let discovery = find_by_extension(settings.folder.as_str(), ALS, &settings.discovery);
+
+    if discovery.files.is_empty() {
+        return Err(Error::FileNotFound("No Ableton Live project files found".to_string()));
+    }
+
+    let mut report = RunReport::new(job_id, JobKind::Copify);
+    report.scan_errors = discovery.errors;
+    report.projects = copify_files(discovery.files, settings, cancel, reporter)?;
+    report.finish();
+    reporter.flush();
+
+    Ok(report)
+}
+
+/// Run copify on a single set, e.g. one that was just saved
+pub fn copify_set(
+    job_id: &str,
+    cancel: &CancelToken,
+    settings: &CopifySettings,
+    set: &Path,
+    reporter: &Reporter,
+) -> Result<RunReport, Error> {
+    let mut report = RunReport::new(job_id, JobKind::Copify);
+    report.projects = copify_files(vec![set.to_path_buf()], settings, cancel, reporter)?;
+    report.finish();
+    reporter.flush();
+
+    Ok(report)
+}
+
+/// Run copify on every project file on a bounded worker pool and
+/// report the outcome of each file.
+///
+/// Files of the same project folder share their `Samples/Imported`
+/// destination, so they are always handled one after another by the
+/// same worker.
+/// Files matching `settings.exclude_files` are skipped.
+pub fn copify_files(
+    files: Vec<PathBuf>,
+    settings: &CopifySettings,
+    cancel: &CancelToken,
+    reporter: &Reporter,
+) -> Result<Vec<ProjectReport>, Error> {
+    let job_id = reporter.job_id();
+    let exclusions = Exclusions::new(Path::new(&settings.folder), &settings.exclude_files)?;
+    let total = files.len();
+    let done = AtomicUsize::new(0);
+    let projects = Mutex::new(Vec::with_capacity(total));
+
+    // Known before the first sample is copied, so the ETA means something
+    reporter.add_total(
+        files
+            .iter()
+            .filter(|f| !is_backup_folder(f) && !exclusions.is_excluded(f))
+            .map(|f| referenced_bytes(f, settings))
+            .sum(),
+    );
+
+    run_grouped(
+        files,
+        |file_path| project_root(file_path, Path::new(&settings.folder)),
+        settings.concurrency,
+        |file_path| {
+            let progress_value = ((done.fetch_add(1, Ordering::SeqCst) + 1) * 100) / total;
+            let file_name_str = file_path.to_string_lossy().to_string();
+
+            // Stop at a project boundary, everything left is reported as not processed
+            if cancel.is_cancelled() {
+                let project = ProjectReport::new(file_name_str, ProjectStatus::NotProcessed);
+                projects.lock().unwrap().push(project);
+                return;
+            }
+
+            if exclusions.is_excluded(&file_path) {
+                let project = ProjectReport::new(file_name_str.clone(), ProjectStatus::Skipped);
+                projects.lock().unwrap().push(project);
+                reporter.project(on_skip(job_id, file_name_str, progress_value));
+                return;
+            }
+
+            let started = Instant::now();
+            let result = run_copify(&file_path, settings, cancel, reporter);
+
+            let (mut project, progress) = match result {
+                Ok(stats) => {
+                    let mut project = ProjectReport::new(file_name_str.clone(), ProjectStatus::Processed);
+                    project.samples_collected = stats.collected;
+                    project.samples_skipped = stats.skipped;
+                    project.samples_missing = stats.missing;
+                    project.samples_mismatched = stats.mismatched;
+                    project.bytes_copied = stats.bytes_copied;
+                    (project, on_success(job_id, file_name_str, progress_value))
+                }
+                Err(Error::Cancelled) => (
+                    ProjectReport::new(file_name_str.clone(), ProjectStatus::NotProcessed),
+                    on_skip(job_id, file_name_str, progress_value),
+                ),
+                Err(e) => {
+                    let mut project = ProjectReport::new(file_name_str.clone(), ProjectStatus::Failed);
+                    project.error = Some(e.to_string());
+                    (project, on_error(job_id, file_name_str, progress_value, e.to_string()))
+                }
+            };
+
+            project.duration_ms = started.elapsed().as_millis() as u64;
+            projects.lock().unwrap().push(project);
+            reporter.project(progress);
+        },
+    );
+
+    Ok(projects.into_inner().unwrap())
+}
+
+/// Find a project files samples and move its samples into its folder.
+///
+/// When the job is cancelled or fails half way the set is rolled back:
+/// the original project file is left untouched and samples copied
+/// for it are removed again.
+///
+/// # Arguments
+///
+/// * `file_path` - Ableton project file
+/// * `settings` - Copify process settings
+/// * `cancel` - Token of the job the set belongs to
+/// * `reporter` - Progress events of the job the set belongs to
+pub fn run_copify(
+    file_path: &PathBuf,
+    settings: &CopifySettings,
+    cancel: &CancelToken,
+    reporter: &Reporter,
+) -> Result<SampleStats, Error> {
+    // Skip project files that are in the Ableton Backup folder
+    if is_backup_folder(file_path) {
+        return Ok(SampleStats::default());
+    }
+
+    let mut xml = file_path.clone();
+    xml.set_extension(XML);
+
+    if settings.create_backup {
+        create_backup(file_path, &settings.backup_location, settings.backup_retention)?;
+    }
+
+    let mut stats = SampleStats::default();
+    reporter.phase(Phase::Parsing, Some(file_path));
+    let result = decompress(file_path, xml.as_path())
+        .and_then(|_| {
+            reporter.phase(Phase::Copying, Some(file_path));
+            update_sample_refs(xml.as_path(), settings, cancel, reporter, &mut stats)
+        })
+        .and_then(|_| {
+            if cancel.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+            reporter.phase(Phase::Compressing, Some(file_path));
+            // Write next to the set first so a failed write never truncates it
+            let mut compressed = file_path.clone();
+            compressed.set_extension("als.tmp");
+            compress(xml.as_path(), compressed.as_path())?;
+            fs::rename(&compressed, file_path).between(Operation::Move, &compressed, file_path)
+        });
+
+    if result.is_err() {
+        for sample in &stats.new_files {
+            let _ = fs::remove_file(sample);
+        }
+    }
+
+    // Intermediate files of update_sample_refs are left behind when it fails
+    let rewritten = xml.with_extension("tmp.xml");
+    if rewritten.exists() {
+        fs::remove_file(&rewritten).on(Operation::Remove, &rewritten)?;
+    }
+
+    if xml.exists() {
+        fs::remove_file(&xml).on(Operation::Remove, &xml)?;
+    }
+
+    result.map(|_| stats)
+}
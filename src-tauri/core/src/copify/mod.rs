@@ -0,0 +1,10 @@
+pub(crate) mod backup;
+pub(crate) mod copify;
+mod models;
+mod xml;
+
+use xml::*;
+
+pub use backup::*;
+pub use copify::*;
+pub use models::*;
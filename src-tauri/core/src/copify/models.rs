@@ -0,0 +1,64 @@
+use crate::utils::{CopyOptions, DiscoveryOptions};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CopifySettings {
+    pub serum_noises: bool,
+    pub move_samples: bool,
+    pub create_backup: bool,
+    pub folder: String,
+    pub exclude_files: Vec<String>,
+    #[serde(default)]
+    pub backup_location: BackupLocation,
+    #[serde(default = "default_backup_retention")]
+    pub backup_retention: usize,
+    #[serde(default)]
+    pub concurrency: usize,
+    #[serde(default)]
+    pub discovery: DiscoveryOptions,
+    #[serde(default)]
+    pub copy_options: CopyOptions,
+}
+
+impl CopifySettings {
+    /// These settings applied to a single project that is collected
+    /// before it is archived or shared. Its sets are rewritten in place,
+    /// so a backup of each is always kept.
+    pub fn for_project(mut self, project: &str) -> Self {
+        self.folder = project.to_string();
+        self.create_backup = true;
+        self
+    }
+}
+
+/// Where timestamped `.als.bak` backups of a set are written.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "folder")]
+pub enum BackupLocation {
+    /// Next to the set inside the project folder
+    #[default]
+    Project,
+    /// In a central folder, grouped by the set's original folder
+    Central(String),
+}
+
+/// Backups kept per set when the frontend does not send a value.
+/// `0` keeps every backup.
+pub fn default_backup_retention() -> usize {
+    10
+}
+
+/// What happened to the samples of a single set
+#[derive(Debug, Default)]
+pub struct SampleStats {
+    pub collected: usize,
+    pub skipped: usize,
+    pub missing: usize,
+    // Copies that did not match the sample, the set still uses the original
+    pub mismatched: usize,
+    pub bytes_copied: u64,
+    // Samples that did not exist in the project folder before, removed again on rollback
+    pub new_files: Vec<PathBuf>,
+}
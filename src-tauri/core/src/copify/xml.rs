@@ -0,0 +1,425 @@
+use crate::copify::{CopifySettings, SampleStats};
+use crate::jobs::CancelToken;
+use crate::utils::{
+    copy_sample, decode_xml_value, project_root, sample_destination, Reporter, SampleStatus, ALS,
+    SAMPLES_IMPORTED,
+};
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::QName;
+use quick_xml::{Reader, Writer};
+use std::borrow::Cow;
+use std::fs;
+use std::fs::rename;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use flate2::read::GzDecoder;
+use crate::error::{Error, IoContext, Operation};
+
+/// Finds sample files in an Ableton Live project file
+/// and does the following:
+///
+/// * Copies it to the project folder, see `project_root`
+/// * Set <SampleRef /> XML tag in project file with new sample path
+/// * Overwrite previous XML
+///
+/// Samples that did not exist in the project folder before are added to
+/// `stats` so the caller can roll them back. Returns `Error::Cancelled`
+/// before the next sample is copied once `cancel` is set.
+///
+/// Samples that cannot be found, are skipped or whose copy does not
+/// match are left untouched, both their `Path` and `RelativePath`.
+///
+pub fn update_sample_refs(
+    xml_path: &Path,
+    settings: &CopifySettings,
+    cancel: &CancelToken,
+    reporter: &Reporter,
+    stats: &mut SampleStats,
+) -> Result<(), Error> {
+    let set = xml_path.with_extension(ALS);
+    // Sets in subfolders like `Versions` collect into the project folder
+    let dir = project_root(&set, Path::new(&settings.folder)).unwrap_or_else(|| xml_path.parent().unwrap().to_path_buf());
+    let dir = dir.as_path();
+    let temp_path = xml_path.with_extension("tmp.xml");
+
+    // Open the input file for reading
+    let file = File::open(xml_path).on(Operation::Read, xml_path)?;
+    let mut reader = Reader::from_reader(BufReader::new(file));
+    reader.trim_text(true);
+
+    // Prepare a writer for the temporary output file
+    let temp_file = File::create(&temp_path).on(Operation::Create, &temp_path)?;
+    let mut writer = Writer::new(BufWriter::new(temp_file));
+
+    let mut buf = Vec::new();
+    let mut inside_sample_ref = false;
+    // File name of the collected copy once the `Path` of the current
+    // sample was handled, `None` inside when it was not collected
+    let mut collected: Option<Option<String>> = None;
+    // A `RelativePath` seen before its `Path`, with everything after it
+    let mut held: Option<Held> = None;
+
+    loop {
+        let event = match reader.read_event_into(&mut buf) {
+            Ok(Event::Eof) => break,
+            Ok(event) => event.into_owned(),
+            // Never write back a partially parsed set
+            Err(e) => {
+                return Err(Error::Xml(format!(
+                    "{} at position {}: {}",
+                    set.display(),
+                    reader.buffer_position(),
+                    e
+                )));
+            }
+        };
+        buf.clear();
+
+        match event {
+            Event::Decl(_) | Event::Text(_) => emit(&mut writer, &mut held, event)?,
+            Event::Start(ref e) => {
+                if e.name().0 == b"SampleRef" {
+                    inside_sample_ref = true;
+                }
+                emit(&mut writer, &mut held, event)?;
+            }
+            Event::End(ref e) => {
+                if e.name().0 == b"SampleRef" {
+                    inside_sample_ref = false;
+                    collected = None;
+                    // A sample without `Path` keeps its relative path
+                    if let Some((relative, events)) = held.take() {
+                        write_held(&mut writer, relative, events, None)?;
+                    }
+                }
+                emit(&mut writer, &mut held, event)?;
+            }
+            Event::Empty(e) if inside_sample_ref && e.name().0 == b"RelativePath" => match &collected {
+                Some(name) => write_held(&mut writer, e, Vec::new(), name.as_deref())?,
+                None => held = Some((e, Vec::new())),
+            },
+            Event::Empty(mut e) if inside_sample_ref && e.name().0 == b"Path" => {
+                let name = collect_sample(&mut e, &set, dir, settings, cancel, reporter, stats)?;
+                if let Some((relative, events)) = held.take() {
+                    write_held(&mut writer, relative, events, name.as_deref())?;
+                }
+                writer.write_event(Event::Empty(e))?;
+                collected = Some(name);
+            }
+            Event::Empty(_) => emit(&mut writer, &mut held, event)?,
+            _ => {}
+        }
+    }
+
+    // Close the writer to ensure all content is flushed to temp file
+    writer.into_inner().flush().on(Operation::Write, &temp_path)?;
+
+    // Replace original file with the temporary output file
+    rename(&temp_path, xml_path).between(Operation::Move, &temp_path, xml_path)?;
+
+    Ok(())
+}
+
+/// A `RelativePath` element waiting for the outcome of its sample, and
+/// the events that followed it
+type Held = (BytesStart<'static>, Vec<Event<'static>>);
+
+type XmlWriter = Writer<BufWriter<File>>;
+
+fn emit(writer: &mut XmlWriter, held: &mut Option<Held>, event: Event<'static>) -> Result<(), Error> {
+    match held {
+        Some((_, events)) => events.push(event),
+        None => writer.write_event(event)?,
+    }
+    Ok(())
+}
+
+/// Write a held `RelativePath`, pointing it at `Samples/Imported` when
+/// its sample was collected as `collected`
+fn write_held(
+    writer: &mut XmlWriter,
+    mut relative: BytesStart<'static>,
+    events: Vec<Event<'static>>,
+    collected: Option<&str>,
+) -> Result<(), Error> {
+    if let Some(name) = collected {
+        modify_value_attribute(&mut relative, "Value", &format!("{}{}", SAMPLES_IMPORTED, name));
+    }
+    writer.write_event(Event::Empty(relative))?;
+    for event in events {
+        writer.write_event(event)?;
+    }
+    Ok(())
+}
+
+/// Collect the sample a `Path` element points at and point the element
+/// at the copy. Returns the file name of the copy when it was collected.
+fn collect_sample(
+    path_element: &mut BytesStart,
+    set: &Path,
+    dir: &Path,
+    settings: &CopifySettings,
+    cancel: &CancelToken,
+    reporter: &Reporter,
+    stats: &mut SampleStats,
+) -> Result<Option<String>, Error> {
+    let Some(path_value) = get_value_attribute(path_element, settings) else {
+        if let Some(path_value) = value_attribute(path_element) {
+            stats.skipped += 1;
+            reporter.sample(set, &decode_xml_value(&path_value), SampleStatus::Skipped);
+        }
+        return Ok(None);
+    };
+
+    if cancel.is_cancelled() {
+        return Err(Error::Cancelled);
+    }
+    let sanitized = decode_xml_value(&path_value);
+    if !Path::new(&sanitized).is_file() {
+        stats.missing += 1;
+        reporter.sample(set, &sanitized, SampleStatus::Missing);
+        return Ok(None);
+    }
+
+    let destination = sample_destination(&sanitized, dir)?;
+    let is_new = !destination.exists();
+    let size = fs::metadata(&sanitized).map(|m| m.len()).unwrap_or(0);
+    match copy_sample(&sanitized, dir, &settings.copy_options) {
+        Ok((absolute, bytes)) => {
+            reporter.advance(size);
+            reporter.sample(set, &sanitized, SampleStatus::Copied);
+            stats.collected += 1;
+            stats.bytes_copied += bytes;
+            // Modify the Path element's Value attribute with the new absolute path
+            modify_value_attribute(path_element, "Value", &absolute);
+            let name = destination.file_name().map(|n| n.to_string_lossy().into_owned());
+            if is_new {
+                stats.new_files.push(destination);
+            }
+            Ok(name)
+        }
+        // Not collected, the reference keeps pointing at the intact original
+        Err(Error::ChecksumMismatch { .. }) => {
+            reporter.advance(size);
+            reporter.sample(set, &sanitized, SampleStatus::Mismatched);
+            stats.mismatched += 1;
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Total size of the samples `update_sample_refs` copies for a project
+/// file, read before anything is copied so progress knows its total.
+///
+/// A set that cannot be read counts what was found up to that point.
+pub fn referenced_bytes(set: &Path, settings: &CopifySettings) -> u64 {
+    let file = match File::open(set) {
+        Ok(file) => file,
+        Err(_) => return 0,
+    };
+    let mut reader = Reader::from_reader(BufReader::new(GzDecoder::new(file)));
+    reader.trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut inside_sample_ref = false;
+    let mut bytes = 0;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) if e.name().0 == b"SampleRef" => inside_sample_ref = true,
+            Ok(Event::End(ref e)) if e.name().0 == b"SampleRef" => inside_sample_ref = false,
+            Ok(Event::Empty(ref e)) if inside_sample_ref && e.name().0 == b"Path" => {
+                if let Some(path_value) = get_value_attribute(e, settings) {
+                    bytes += fs::metadata(decode_xml_value(&path_value))
+                        .ok()
+                        .filter(|m| m.is_file())
+                        .map(|m| m.len())
+                        .unwrap_or(0);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    bytes
+}
+
+// Helper function to modify the Value attribute in Path element
+fn modify_value_attribute(element: &mut BytesStart, attr_key: &str, new_value: &str) {
+    // Step 1: Collect and clone all attributes to avoid borrowing `element`
+    let mut new_attributes: Vec<Attribute> = Vec::new();
+    let mut found = false;
+    let clone = element.clone();
+
+    for attr in clone.attributes() {
+        let attr = attr.unwrap(); // Unwrap the Result to get the attribute
+        if attr.key == QName(attr_key.as_bytes()) {
+            // Modify the attribute if it matches `attr_key`
+            new_attributes.push(Attribute {
+                key: attr.key,
+                value: Cow::from(new_value.as_bytes().to_vec()),
+            });
+            found = true;
+        } else {
+            // Otherwise, clone the existing attribute as-is
+            new_attributes.push(attr.clone());
+        }
+    }
+
+    // If the attribute wasn't found, add it to the new attributes
+    if !found {
+        new_attributes.push(Attribute {
+            key: QName(attr_key.as_bytes()),
+            value: Cow::from(new_value.as_bytes().to_vec()),
+        });
+    }
+
+    // Step 2: Clear the existing attributes in `element`
+    element.clear_attributes();
+
+    // Step 3: Push the modified attributes back into `element`
+    for attr in new_attributes {
+        element.push_attribute(attr);
+    }
+}
+
+fn get_value_attribute(e: &BytesStart, settings: &CopifySettings) -> Option<String> {
+    let value = value_attribute(e)?;
+    if settings.serum_noises && value.contains("Serum Presets/Noises") {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn value_attribute(e: &BytesStart) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key == QName(b"Value"))
+        .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::copify::BackupLocation;
+    use crate::utils::NoProgress;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    fn settings(serum_noises: bool) -> CopifySettings {
+        CopifySettings {
+            serum_noises,
+            move_samples: false,
+            create_backup: false,
+            folder: String::new(),
+            exclude_files: Vec::new(),
+            backup_location: BackupLocation::Project,
+            backup_retention: 0,
+            concurrency: 1,
+            discovery: Default::default(),
+            copy_options: Default::default(),
+        }
+    }
+
+    /// Run `update_sample_refs` on a set in a new project folder and
+    /// return the rewritten XML
+    fn rewrite(samples: &str, settings: &mut CopifySettings) -> (String, SampleStats) {
+        let dir = tempfile::tempdir().unwrap();
+        let xml_path = dir.path().join("Song.xml");
+        fs::write(&xml_path, format!("<Ableton>{}</Ableton>", samples)).unwrap();
+        settings.folder = dir.path().to_string_lossy().into_owned();
+
+        let mut stats = SampleStats::default();
+        let reporter = Reporter::new("job", NoProgress);
+        update_sample_refs(&xml_path, settings, &CancelToken::default(), &reporter, &mut stats).unwrap();
+
+        (fs::read_to_string(&xml_path).unwrap(), stats)
+    }
+
+    fn sample_ref(relative: &str, path: &str) -> String {
+        format!(
+            r#"<SampleRef><FileRef><RelativePath Value="{}"/><Path Value="{}"/></FileRef></SampleRef>"#,
+            relative, path
+        )
+    }
+
+    #[test]
+    fn points_collected_samples_at_the_copy() {
+        let library = tempfile::tempdir().unwrap();
+        let kick = library.path().join("kick.wav");
+        fs::write(&kick, "kick").unwrap();
+
+        // Live versions differ in the order of `Path` and `RelativePath`
+        let path_first = format!(
+            r#"<SampleRef><FileRef><Path Value="{}"/><RelativePath Value="../kick.wav"/></FileRef></SampleRef>"#,
+            kick.display()
+        );
+        let samples = sample_ref("../kick.wav", &kick.display().to_string()) + &path_first;
+        let (xml, stats) = rewrite(&samples, &mut settings(false));
+
+        assert_eq!(stats.collected, 2);
+        assert_eq!(xml.matches(r#"<RelativePath Value="Samples/Imported/kick.wav"/>"#).count(), 2);
+        assert!(!xml.contains(&kick.display().to_string()));
+    }
+
+    #[test]
+    fn leaves_missing_and_skipped_samples_untouched() {
+        let missing = sample_ref("../missing.wav", "/nowhere/missing.wav");
+        let noise = sample_ref("../hiss.wav", "/Serum Presets/Noises/hiss.wav");
+        let (xml, stats) = rewrite(&(missing.clone() + &noise), &mut settings(true));
+
+        assert_eq!((stats.missing, stats.skipped), (1, 1));
+        assert!(xml.contains(&missing), "{}", xml);
+        assert!(xml.contains(&noise), "{}", xml);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn leaves_mismatched_samples_untouched() {
+        // Every read returns another UUID, so no copy ever matches
+        let changing = sample_ref("../uuid", "/proc/sys/kernel/random/uuid");
+        let mut settings = settings(false);
+        settings.copy_options.verify = true;
+        let (xml, stats) = rewrite(&changing, &mut settings);
+
+        assert_eq!(stats.mismatched, 1);
+        assert!(xml.contains(&changing), "{}", xml);
+    }
+
+    #[test]
+    fn counts_every_existing_sample_reference() {
+        let folder = std::env::temp_dir().join(format!("livesaver-refs-{}", std::process::id()));
+        let noises = folder.join("Serum Presets/Noises");
+        fs::create_dir_all(&noises).unwrap();
+        fs::write(folder.join("kick.wav"), [0; 10]).unwrap();
+        fs::write(noises.join("hiss.wav"), [0; 100]).unwrap();
+
+        let path = |name: &str| format!(r#"<SampleRef><Path Value="{}"/></SampleRef>"#, folder.join(name).display());
+        let xml = format!(
+            "<Ableton>{}{}{}{}<Path Value=\"{}\"/></Ableton>",
+            path("kick.wav"),
+            path("kick.wav"),
+            path("missing.wav"),
+            path("Serum Presets/Noises/hiss.wav"),
+            folder.join("kick.wav").display(),
+        );
+        let set = folder.join("Song.als");
+        let mut encoder = GzEncoder::new(File::create(&set).unwrap(), Compression::default());
+        encoder.write_all(xml.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        // Copied once per reference, outside a SampleRef nothing is copied
+        assert_eq!(referenced_bytes(&set, &settings(false)), 120);
+        assert_eq!(referenced_bytes(&set, &settings(true)), 20);
+
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
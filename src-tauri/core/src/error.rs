@@ -0,0 +1,186 @@
+//! Main Crate Error
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Generic error: {0}")]
+    Generic(String),
+
+    #[error("FileNotFound error: {0}")]
+    FileNotFound(String),
+
+    #[error("Copify error: {0}")]
+    CopifyFailed(String),
+
+    #[error("Mover error: {0}")]
+    MoverFailed(String),
+
+    #[error("Xml error: {0}")]
+    Xml(String),
+
+    #[error("Archive error: {0}")]
+    Archive(String),
+
+    #[error("Job was cancelled")]
+    Cancelled,
+
+    /// A verified copy still differed from its source after every retry
+    #[error("Copy of {} does not match {}", copy.display(), original.display())]
+    ChecksumMismatch { original: PathBuf, copy: PathBuf },
+
+    /// A filesystem operation failed on one or more paths
+    #[error("{}", describe_io(source, *operation, paths))]
+    Io {
+        operation: Operation,
+        paths: Vec<PathBuf>,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Stable error codes the frontend can match on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Generic,
+    FileNotFound,
+    CopifyFailed,
+    MoverFailed,
+    Xml,
+    Archive,
+    Cancelled,
+    ChecksumMismatch,
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    StorageFull,
+    CrossesDevices,
+    Io,
+}
+
+/// The filesystem operation that was running when an error occurred
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    Read,
+    Write,
+    Create,
+    Copy,
+    Move,
+    Remove,
+    List,
+    Compress,
+    Decompress,
+}
+
+impl Error {
+    pub fn io(operation: Operation, path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Error::Io { operation, paths: vec![path.into()], source }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::Generic(_) => ErrorCode::Generic,
+            Error::FileNotFound(_) => ErrorCode::FileNotFound,
+            Error::CopifyFailed(_) => ErrorCode::CopifyFailed,
+            Error::MoverFailed(_) => ErrorCode::MoverFailed,
+            Error::Xml(_) => ErrorCode::Xml,
+            Error::Archive(_) => ErrorCode::Archive,
+            Error::Cancelled => ErrorCode::Cancelled,
+            Error::ChecksumMismatch { .. } => ErrorCode::ChecksumMismatch,
+            Error::Io { source, .. } => match source.kind() {
+                io::ErrorKind::NotFound => ErrorCode::NotFound,
+                io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+                io::ErrorKind::AlreadyExists => ErrorCode::AlreadyExists,
+                io::ErrorKind::StorageFull => ErrorCode::StorageFull,
+                io::ErrorKind::CrossesDevices => ErrorCode::CrossesDevices,
+                _ => ErrorCode::Io,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = match self {
+            Operation::Read => "reading",
+            Operation::Write => "writing",
+            Operation::Create => "creating",
+            Operation::Copy => "copying",
+            Operation::Move => "moving",
+            Operation::Remove => "removing",
+            Operation::List => "listing",
+            Operation::Compress => "compressing",
+            Operation::Decompress => "decompressing",
+        };
+        f.write_str(verb)
+    }
+}
+
+/// e.g. "permission denied writing /X" or "not found copying /a to /b"
+fn describe_io(source: &io::Error, operation: Operation, paths: &[PathBuf]) -> String {
+    let kind = source.kind().to_string();
+    let paths = paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" to ");
+    format!("{} {} {}", kind, operation, paths)
+}
+
+/// Serialized as `{ code, message, operation, paths, cause }` so the
+/// frontend can show `message` and branch on `code`
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (operation, paths, cause) = match self {
+            Error::Io { operation, paths, source } => (Some(*operation), paths.clone(), Some(source.to_string())),
+            Error::ChecksumMismatch { original, copy } => (Some(Operation::Copy), vec![original.clone(), copy.clone()], None),
+            _ => (None, Vec::new(), None),
+        };
+
+        let mut state = serializer.serialize_struct("Error", 5)?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("operation", &operation)?;
+        state.serialize_field("paths", &paths)?;
+        state.serialize_field("cause", &cause)?;
+        state.end()
+    }
+}
+
+/// Attach the operation and paths to a failed `io::Result`
+pub trait IoContext<T> {
+    fn on(self, operation: Operation, path: &Path) -> Result<T, Error>;
+    fn between(self, operation: Operation, from: &Path, to: &Path) -> Result<T, Error>;
+}
+
+impl<T> IoContext<T> for io::Result<T> {
+    fn on(self, operation: Operation, path: &Path) -> Result<T, Error> {
+        self.map_err(|source| Error::io(operation, path, source))
+    }
+
+    fn between(self, operation: Operation, from: &Path, to: &Path) -> Result<T, Error> {
+        self.map_err(|source| Error::Io {
+            operation,
+            paths: vec![from.to_path_buf(), to.to_path_buf()],
+            source,
+        })
+    }
+}
+
+impl From<quick_xml::Error> for Error {
+    fn from(err: quick_xml::Error) -> Self {
+        Error::Xml(err.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for Error {
+    fn from(err: zip::result::ZipError) -> Self {
+        Error::Archive(err.to_string())
+    }
+}
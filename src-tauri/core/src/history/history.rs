@@ -0,0 +1,35 @@
+use crate::error::Error;
+use crate::history::models::{JobOutcome, JobRecord, JobSettings};
+use crate::history::store::History;
+use crate::report::RunReport;
+use chrono::{DateTime, Local};
+
+/// Store the outcome of a finished job. A history that cannot be written
+/// is logged but never fails the job itself.
+pub fn record_job(
+    history: &History,
+    job_id: &str,
+    settings: JobSettings,
+    started_at: DateTime<Local>,
+    result: &Result<RunReport, Error>,
+) {
+    let (outcome, report) = match result {
+        Ok(report) if report.cancelled => (JobOutcome::Cancelled, Some(report.clone())),
+        Ok(report) => (JobOutcome::Completed, Some(report.clone())),
+        Err(Error::Cancelled) => (JobOutcome::Cancelled, None),
+        Err(e) => (JobOutcome::Failed { error: e.to_string() }, None),
+    };
+
+    let record = JobRecord {
+        job_id: job_id.to_string(),
+        settings,
+        started_at,
+        finished_at: Local::now(),
+        outcome,
+        report,
+    };
+
+    if let Err(e) = history.record(record) {
+        log::error!("Failed to record job {} in history: {}", job_id, e);
+    }
+}
@@ -0,0 +1,7 @@
+pub(crate) mod history;
+mod models;
+mod store;
+
+pub use history::*;
+pub use models::*;
+pub use store::*;
@@ -0,0 +1,89 @@
+use crate::copify::CopifySettings;
+use crate::jobs::JobKind;
+use crate::mover::MoverSettings;
+use crate::report::RunReport;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::path::Path;
+
+/// Settings a job was started with, kept so it can be re-run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "settings", rename_all = "snake_case")]
+pub enum JobSettings {
+    Copify(CopifySettings),
+    Mover(MoverSettings),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JobOutcome {
+    Completed,
+    Cancelled,
+    Failed { error: String },
+}
+
+/// A finished job as stored in the history file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub job_id: String,
+    pub settings: JobSettings,
+    pub started_at: DateTime<Local>,
+    pub finished_at: DateTime<Local>,
+    pub outcome: JobOutcome,
+    pub report: Option<RunReport>,
+}
+
+/// A job record without its report, returned when listing the history
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobEntry {
+    pub job_id: String,
+    pub kind: JobKind,
+    pub folder: String,
+    pub started_at: DateTime<Local>,
+    pub finished_at: DateTime<Local>,
+    pub outcome: JobOutcome,
+    pub projects: usize,
+}
+
+impl JobSettings {
+    pub fn kind(&self) -> JobKind {
+        match self {
+            JobSettings::Copify(_) => JobKind::Copify,
+            JobSettings::Mover(_) => JobKind::Mover,
+        }
+    }
+
+    pub fn folder(&self) -> &str {
+        match self {
+            JobSettings::Copify(settings) => &settings.folder,
+            JobSettings::Mover(settings) => &settings.folder,
+        }
+    }
+}
+
+impl JobRecord {
+    /// Whether the job scanned `path` or processed a project file below it
+    pub fn touches(&self, path: &Path) -> bool {
+        let folder = Path::new(self.settings.folder());
+
+        path.starts_with(folder)
+            || folder.starts_with(path)
+            || self
+                .report
+                .as_ref()
+                .is_some_and(|r| r.projects.iter().any(|p| Path::new(&p.file_name).starts_with(path)))
+    }
+
+    pub fn entry(&self) -> JobEntry {
+        JobEntry {
+            job_id: self.job_id.clone(),
+            kind: self.settings.kind(),
+            folder: self.settings.folder().to_string(),
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            outcome: self.outcome.clone(),
+            projects: self.report.as_ref().map(|r| r.projects.len()).unwrap_or_default(),
+        }
+    }
+}
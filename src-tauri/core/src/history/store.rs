@@ -0,0 +1,116 @@
+use crate::error::{Error, IoContext, Operation};
+use crate::utils::{write_atomic, HISTORY_LIMIT};
+use crate::history::models::JobRecord;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// File name of the history inside the app data folder
+pub static HISTORY_FILE: &str = "history.json";
+
+#[derive(Default, Serialize, Deserialize)]
+struct HistoryFile {
+    jobs: Vec<JobRecord>,
+}
+
+/// JSON backed store of finished jobs in the app data folder, safe to
+/// share between threads
+pub struct History {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl History {
+    pub fn open(data_dir: &Path) -> Self {
+        History {
+            path: data_dir.join(HISTORY_FILE),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// All jobs, newest first
+    pub fn list(&self) -> Result<Vec<JobRecord>, Error> {
+        let _guard = self.lock.lock().unwrap();
+        let mut jobs = self.read()?.jobs;
+        jobs.reverse();
+        Ok(jobs)
+    }
+
+    pub fn get(&self, job_id: &str) -> Result<JobRecord, Error> {
+        let _guard = self.lock.lock().unwrap();
+        self.read()?
+            .jobs
+            .into_iter()
+            .find(|job| job.job_id == job_id)
+            .ok_or_else(|| Error::Generic(format!("No job with id {} in history", job_id)))
+    }
+
+    /// Add a finished job, dropping the oldest beyond `HISTORY_LIMIT`
+    pub fn record(&self, job: JobRecord) -> Result<(), Error> {
+        let _guard = self.lock.lock().unwrap();
+        let mut history = self.read()?;
+        history.jobs.push(job);
+        let excess = history.jobs.len().saturating_sub(HISTORY_LIMIT);
+        history.jobs.drain(..excess);
+        self.write(&history)
+    }
+
+    fn read(&self) -> Result<HistoryFile, Error> {
+        if !self.path.exists() {
+            return Ok(HistoryFile::default());
+        }
+
+        let content = fs::read_to_string(&self.path).on(Operation::Read, &self.path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| Error::Generic(format!("Invalid history file {}: {}", self.path.display(), e)))
+    }
+
+    fn write(&self, history: &HistoryFile) -> Result<(), Error> {
+        let content = serde_json::to_string(history).map_err(|e| Error::Generic(e.to_string()))?;
+        write_atomic(&self.path, &content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{JobOutcome, JobSettings};
+    use chrono::Local;
+
+    fn job(job_id: String) -> JobRecord {
+        let settings = serde_json::from_value(serde_json::json!({
+            "serum_noises": false,
+            "move_samples": false,
+            "create_backup": true,
+            "folder": "/music",
+            "exclude_files": [],
+        }))
+        .unwrap();
+
+        JobRecord {
+            job_id,
+            settings: JobSettings::Copify(settings),
+            started_at: Local::now(),
+            finished_at: Local::now(),
+            outcome: JobOutcome::Completed,
+            report: None,
+        }
+    }
+
+    #[test]
+    fn keeps_only_the_newest_jobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(dir.path());
+
+        for i in 0..HISTORY_LIMIT + 3 {
+            history.record(job(i.to_string())).unwrap();
+        }
+
+        let jobs = history.list().unwrap();
+        assert_eq!(jobs.len(), HISTORY_LIMIT);
+        assert_eq!(jobs[0].job_id, (HISTORY_LIMIT + 2).to_string());
+        assert!(history.get("2").is_err());
+        assert!(history.get("3").is_ok());
+    }
+}
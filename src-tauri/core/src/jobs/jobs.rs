@@ -0,0 +1,54 @@
+use crate::jobs::models::CancelToken;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Registry of running jobs so they can be cancelled by id
+#[derive(Default)]
+pub struct Jobs {
+    running: Mutex<HashMap<String, CancelToken>>,
+    counter: AtomicUsize,
+}
+
+/// A registered job. The job is removed from the registry when dropped.
+pub struct JobHandle<'a> {
+    pub id: String,
+    pub token: CancelToken,
+    jobs: &'a Jobs,
+}
+
+impl Jobs {
+    pub fn start(&self) -> JobHandle<'_> {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        let id = format!("job-{}-{}", millis, self.counter.fetch_add(1, Ordering::SeqCst));
+        let token = CancelToken::default();
+
+        self.running.lock().unwrap().insert(id.clone(), token.clone());
+
+        JobHandle { id, token, jobs: self }
+    }
+
+    pub fn cancel(&self, job_id: &str) -> bool {
+        match self.running.lock().unwrap().get(job_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn finish(&self, job_id: &str) {
+        self.running.lock().unwrap().remove(job_id);
+    }
+}
+
+impl Drop for JobHandle<'_> {
+    fn drop(&mut self) {
+        self.jobs.finish(&self.id);
+    }
+}
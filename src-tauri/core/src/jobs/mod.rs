@@ -0,0 +1,5 @@
+pub(crate) mod jobs;
+mod models;
+
+pub use jobs::*;
+pub use models::*;
@@ -0,0 +1,23 @@
+//! The LiveSaver engine: scanning, collecting samples into projects,
+//! moving projects, archives, share packages, backups, reports, history and presets.
+//!
+//! Nothing here depends on Tauri. Progress is sent to a [`ProgressSink`]
+//! so the engine can be driven from the app, the CLI or a test.
+//!
+//! [`ProgressSink`]: utils::ProgressSink
+
+// Feature folders hold a file of the same name, e.g. `copify/copify.rs`
+#![allow(clippy::module_inception)]
+
+pub mod archive;
+pub mod copify;
+pub mod error;
+pub mod history;
+pub mod jobs;
+pub mod mover;
+pub mod prelude;
+pub mod presets;
+pub mod report;
+pub mod share;
+pub mod utils;
+pub mod watch;
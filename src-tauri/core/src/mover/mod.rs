@@ -0,0 +1,5 @@
+mod models;
+pub(crate) mod mover;
+
+pub use models::*;
+pub use mover::*;
@@ -0,0 +1,51 @@
+use crate::copify::{default_backup_retention, BackupLocation};
+use crate::utils::{ConflictPolicy, CopyOptions, DiscoveryOptions};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MoverSettings {
+    // Move project files
+    pub move_project_files: bool,
+    // Include Serum noises
+    pub serum_noises: bool,
+    // Move or copy samples
+    pub move_samples: bool,
+    // Backup xml before modifying
+    pub create_backup: bool,
+    // Input folder to scan
+    pub folder: String,
+    // Target folder
+    pub target: String,
+    // Path of each project inside the target, e.g. `{year}/{project}`
+    #[serde(default = "default_target_template")]
+    pub target_template: String,
+    // Keep the folders between `folder` and each project under the target
+    #[serde(default)]
+    pub preserve_hierarchy: bool,
+    // Exclude project files
+    pub exclude_files: Vec<String>,
+    // Where backups are written
+    #[serde(default)]
+    pub backup_location: BackupLocation,
+    // Number of backups kept per set, 0 keeps all
+    #[serde(default = "default_backup_retention")]
+    pub backup_retention: usize,
+    // Projects processed at the same time, 0 uses all cores
+    #[serde(default)]
+    pub concurrency: usize,
+    // What happens when a project folder already exists in the target
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    // How the input folder is scanned, the target is always skipped
+    #[serde(default)]
+    pub discovery: DiscoveryOptions,
+    // How project folders and samples are copied
+    #[serde(default)]
+    pub copy_options: CopyOptions,
+}
+
+/// Projects go straight into the target, as before templates existed
+pub fn default_target_template() -> String {
+    "{project}".to_string()
+}
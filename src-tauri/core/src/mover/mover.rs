@@ -0,0 +1,226 @@
+use super::models::{MoverSettings};
+use crate::copify::*;
+use crate::jobs::{CancelToken, JobKind};
+use crate::report::{ProjectReport, ProjectStatus, RunReport};
+use crate::utils::*;
+use crate::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Move or copy every project folder found in `settings.folder` to
+/// `settings.target` and run copify on the sets at their new location
+pub fn mover_job(
+    job_id: &str,
+    cancel: &CancelToken,
+    settings: &MoverSettings,
+    reporter: &Reporter,
+) -> Result<RunReport, Error> {
+    reporter.phase(Phase::Scanning, None);
+
+    // Never pick up projects that were already moved into a target inside the folder
+    let mut discovery_options = settings.discovery.clone();
+    discovery_options.skip_folders.push(settings.target.clone().into());
+
+    let discovery = find_by_extension(settings.folder.as_str(), ALS, &discovery_options);
+    let files = discovery.files;
+
+    if files.is_empty() {
+        return Err(Error::FileNotFound("No Ableton Live project files found".to_string()));
+    }
+
+    let mut report = RunReport::new(job_id, JobKind::Mover);
+    report.scan_errors = discovery.errors;
+
+    // Folders are moved as a whole, so a folder with an excluded set is
+    // left where it is together with all of its sets. Sets excluded by a
+    // `.livesaverignore` file were never found, so every folder is searched.
+    let exclusions = Exclusions::new(Path::new(&settings.folder), &settings.exclude_files)?;
+    let mut folders = Vec::new();
+    for folder in project_folders(&files, Path::new(&settings.folder)) {
+        let excluded = exclusions.excluded_below(&folder.root);
+        let Some(first) = excluded.first() else {
+            folders.push(folder);
+            continue;
+        };
+
+        for set in folder.sets.iter().filter(|set| !excluded.contains(set)) {
+            let file_name = set.to_string_lossy().to_string();
+            let mut project = ProjectReport::new(file_name.clone(), ProjectStatus::Skipped);
+            project.error = Some(format!("{} contains the excluded set {}", folder.root.display(), first.display()));
+            report.projects.push(project);
+            reporter.project(on_skip(job_id, file_name, 0));
+        }
+        for set in &excluded {
+            let file_name = set.to_string_lossy().to_string();
+            report.projects.push(ProjectReport::new(file_name.clone(), ProjectStatus::Skipped));
+            reporter.project(on_skip(job_id, file_name, 0));
+        }
+    }
+
+    let copify_settings = CopifySettings {
+        serum_noises: settings.serum_noises,
+        move_samples: settings.move_samples,
+        create_backup: settings.create_backup,
+        folder: settings.target.clone(),
+        // Already applied to the source folder above
+        exclude_files: Vec::new(),
+        backup_location: settings.backup_location.clone(),
+        backup_retention: settings.backup_retention,
+        concurrency: settings.concurrency,
+        discovery: settings.discovery.clone(),
+        copy_options: settings.copy_options.clone(),
+    };
+
+    // An incremental copy updates the target of an earlier run in place
+    let mirror = !settings.move_project_files && settings.copy_options.incremental != Incremental::Off;
+    let mut template = TargetTemplate::parse(&settings.target_template)?;
+    if settings.preserve_hierarchy {
+        template = template.preserving_hierarchy_of(Path::new(&settings.folder));
+    }
+    let plan = plan_moves(folders, settings.target.as_str(), &template, settings.conflict_policy, mirror)?;
+
+    // Projects left where they are because their folder exists in the target
+    for planned in plan.iter().filter(|p| p.conflict.as_ref().is_some_and(|c| c.policy == ConflictPolicy::Skip)) {
+        for set in &planned.folder.sets {
+            let file_name = set.to_string_lossy().to_string();
+            let mut project = ProjectReport::new(file_name.clone(), ProjectStatus::Skipped);
+            project.conflict = planned.conflict.clone();
+            project.error = Some(format!("{} already exists", planned.target.display()));
+            report.projects.push(project);
+            reporter.project(on_skip(job_id, file_name, 0));
+        }
+    }
+
+    let Moves { moved, failed } = move_or_copy_files(plan, settings.move_project_files, &settings.copy_options, cancel, reporter);
+
+    // Folders that could not be moved, their sets are still in the source folder
+    for failed_move in failed {
+        for set in &failed_move.folder.sets {
+            let file_name = set.to_string_lossy().to_string();
+            let mut project = ProjectReport::new(file_name.clone(), ProjectStatus::Failed);
+            project.conflict = failed_move.conflict.clone();
+            project.error = Some(failed_move.error.clone());
+            report.projects.push(project);
+            reporter.project(on_error(job_id, file_name, 0, failed_move.error.clone()));
+        }
+    }
+    let reported: Vec<PathBuf> = report.projects.iter().map(|p| PathBuf::from(&p.file_name)).collect();
+
+    // Files whose folder was never moved because the job was cancelled
+    for file_path in files
+        .iter()
+        .filter(|f| !reported.contains(f) && !moved.iter().any(|m| &m.source == *f))
+    {
+        let project = ProjectReport::new(file_path.to_string_lossy().to_string(), ProjectStatus::NotProcessed);
+        report.projects.push(project);
+    }
+
+    let paths = moved.iter().map(|m| m.file_path.clone()).collect();
+    let mut projects = copify_files(paths, &copify_settings, cancel, reporter)?;
+
+    // Add the bytes of the folder copy to the samples copied by copify
+    for project in projects.iter_mut() {
+        if let Some(m) = moved.iter().find(|m| m.file_path.to_string_lossy() == project.file_name) {
+            project.bytes_copied += m.bytes_copied;
+            project.conflict = m.conflict.clone();
+            // Cancelled after its folder was moved, the set is only in the target
+            if project.status == ProjectStatus::NotProcessed {
+                project.error = Some(format!("Moved from {}, samples were not collected", m.source.display()));
+            }
+        }
+    }
+
+    report.projects.append(&mut projects);
+    report.finish();
+    reporter.flush();
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Settings as sent by the frontend, which leaves out most options
+    fn settings(folder: &Path, target: &Path, move_project_files: bool) -> MoverSettings {
+        serde_json::from_value(serde_json::json!({
+            "move_project_files": move_project_files,
+            "serum_noises": false,
+            "move_samples": false,
+            "create_backup": false,
+            "folder": folder,
+            "target": target,
+            "exclude_files": [],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn leaves_folders_with_ignored_sets_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let (src, dst) = (dir.path().join("src"), dir.path().join("dst"));
+        fs::create_dir_all(src.join("Track1/Versions")).unwrap();
+        fs::write(src.join("Track1/Song.als"), "").unwrap();
+        fs::write(src.join("Track1/Versions/v3.als"), "").unwrap();
+        fs::write(src.join("Track1").join(IGNORE_FILE), "v3.als\n").unwrap();
+
+        let reporter = Reporter::new("job", NoProgress);
+        let report = mover_job("job", &CancelToken::default(), &settings(&src, &dst, true), &reporter).unwrap();
+
+        assert!(src.join("Track1/Versions/v3.als").exists());
+        assert!(!dst.join("Track1").exists());
+        assert_eq!(report.count(ProjectStatus::Skipped), 2);
+        let song = report.projects.iter().find(|p| p.file_name.ends_with("Song.als")).unwrap();
+        assert!(song.error.as_deref().unwrap().contains("v3.als"));
+    }
+
+    #[test]
+    fn reports_where_sets_went_when_cancelled_after_moving() {
+        let dir = tempfile::tempdir().unwrap();
+        let (src, dst) = (dir.path().join("src"), dir.path().join("dst"));
+        for project in ["A", "B"] {
+            fs::create_dir_all(src.join(project).join("Samples")).unwrap();
+            fs::write(src.join(project).join("Song.als"), "").unwrap();
+            fs::write(src.join(project).join("Samples/kick.wav"), "kick").unwrap();
+        }
+
+        // Cancel while the first folder is copied, it still finishes
+        let cancel = CancelToken::default();
+        let sink = {
+            let cancel = cancel.clone();
+            move |event: JobEvent| {
+                if matches!(event.kind, JobEventKind::Bytes { .. }) {
+                    cancel.cancel();
+                }
+            }
+        };
+        let reporter = Reporter::new("job", sink);
+        let report = mover_job("job", &cancel, &settings(&src, &dst, false), &reporter).unwrap();
+
+        assert!(report.cancelled);
+        assert_eq!(report.count(ProjectStatus::NotProcessed), 2);
+        assert!(!dst.join("B").exists());
+
+        let moved = dst.join("A/Song.als").to_string_lossy().to_string();
+        let project = report.projects.iter().find(|p| p.file_name == moved).unwrap();
+        assert_eq!(project.status, ProjectStatus::NotProcessed);
+        assert!(project.error.as_deref().unwrap().contains(&*src.join("A/Song.als").to_string_lossy()));
+    }
+
+    #[test]
+    fn never_moves_onto_an_existing_folder_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let (src, dst) = (dir.path().join("src"), dir.path().join("dst"));
+        fs::create_dir_all(src.join("Song")).unwrap();
+        fs::create_dir_all(dst.join("Song")).unwrap();
+        fs::write(src.join("Song/Song.als"), "source").unwrap();
+        fs::write(dst.join("Song/Song.als"), "target").unwrap();
+
+        let reporter = Reporter::new("job", NoProgress);
+        let result = mover_job("job", &CancelToken::default(), &settings(&src, &dst, true), &reporter);
+
+        assert!(matches!(result, Err(Error::MoverFailed(_))));
+        assert_eq!(fs::read_to_string(src.join("Song/Song.als")).unwrap(), "source");
+        assert_eq!(fs::read_to_string(dst.join("Song/Song.als")).unwrap(), "target");
+    }
+}
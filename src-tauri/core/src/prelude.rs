@@ -1,6 +1,6 @@
 //! Crate prelude
 
-pub use crate::error::Error;
+pub use crate::error::{Error, IoContext, Operation};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -0,0 +1,5 @@
+mod models;
+mod store;
+
+pub use models::*;
+pub use store::*;
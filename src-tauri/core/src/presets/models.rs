@@ -0,0 +1,14 @@
+use crate::history::JobSettings;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Named copify or mover settings such as "Archive to NAS"
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    #[serde(flatten)]
+    pub settings: JobSettings,
+    pub created_at: DateTime<Local>,
+    pub updated_at: DateTime<Local>,
+}
@@ -0,0 +1,232 @@
+use crate::error::{Error, IoContext, Operation};
+use crate::utils::write_atomic;
+use crate::history::JobSettings;
+use crate::presets::models::Preset;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// File name of the presets inside the app data folder
+pub static PRESETS_FILE: &str = "presets.json";
+
+/// Version written by this build. Bump it and add a migration
+/// whenever the stored format changes.
+pub const PRESETS_SCHEMA_VERSION: u64 = 1;
+
+/// Migrations indexed by the version they upgrade from
+const MIGRATIONS: [fn(Value) -> Value; PRESETS_SCHEMA_VERSION as usize] = [migrate_v0];
+
+#[derive(Default, Serialize, Deserialize)]
+struct PresetsFile {
+    schema_version: u64,
+    presets: Vec<Preset>,
+}
+
+/// JSON backed store of named settings presets in the app data folder,
+/// safe to share between threads
+pub struct Presets {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl Presets {
+    pub fn open(data_dir: &Path) -> Self {
+        Presets {
+            path: data_dir.join(PRESETS_FILE),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn list(&self) -> Result<Vec<Preset>, Error> {
+        let _guard = self.lock.lock().unwrap();
+        Ok(self.read()?.presets)
+    }
+
+    pub fn get(&self, name: &str) -> Result<Preset, Error> {
+        let _guard = self.lock.lock().unwrap();
+        self.read()?
+            .presets
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| not_found(name))
+    }
+
+    pub fn save(&self, name: &str, settings: JobSettings) -> Result<Preset, Error> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Error::Generic("Preset name cannot be empty".to_string()));
+        }
+
+        let _guard = self.lock.lock().unwrap();
+        let mut file = self.read()?;
+        let now = Local::now();
+
+        let preset = match file.presets.iter_mut().find(|p| p.name == name) {
+            Some(preset) => {
+                preset.settings = settings;
+                preset.updated_at = now;
+                preset.clone()
+            }
+            None => {
+                let preset = Preset {
+                    name: name.to_string(),
+                    settings,
+                    created_at: now,
+                    updated_at: now,
+                };
+                file.presets.push(preset.clone());
+                preset
+            }
+        };
+
+        self.write(&file)?;
+        Ok(preset)
+    }
+
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<Preset, Error> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(Error::Generic("Preset name cannot be empty".to_string()));
+        }
+
+        let _guard = self.lock.lock().unwrap();
+        let mut file = self.read()?;
+
+        if new_name != name && file.presets.iter().any(|p| p.name == new_name) {
+            return Err(Error::Generic(format!("A preset named {} already exists", new_name)));
+        }
+
+        let preset = file
+            .presets
+            .iter_mut()
+            .find(|p| p.name == name)
+            .ok_or_else(|| not_found(name))?;
+        preset.name = new_name.to_string();
+        preset.updated_at = Local::now();
+        let preset = preset.clone();
+
+        self.write(&file)?;
+        Ok(preset)
+    }
+
+    pub fn delete(&self, name: &str) -> Result<(), Error> {
+        let _guard = self.lock.lock().unwrap();
+        let mut file = self.read()?;
+        let count = file.presets.len();
+        file.presets.retain(|p| p.name != name);
+
+        if file.presets.len() == count {
+            return Err(not_found(name));
+        }
+
+        self.write(&file)
+    }
+
+    fn read(&self) -> Result<PresetsFile, Error> {
+        if !self.path.exists() {
+            return Ok(PresetsFile {
+                schema_version: PRESETS_SCHEMA_VERSION,
+                presets: Vec::new(),
+            });
+        }
+
+        let content = fs::read_to_string(&self.path).on(Operation::Read, &self.path)?;
+        let value: Value = serde_json::from_str(&content).map_err(|e| self.invalid(e))?;
+        let (value, migrated) = migrate(value)?;
+        let file: PresetsFile = serde_json::from_value(value).map_err(|e| self.invalid(e))?;
+
+        // Persist migrated presets so older files are only upgraded once
+        if migrated {
+            self.write(&file)?;
+        }
+
+        Ok(file)
+    }
+
+    fn write(&self, file: &PresetsFile) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(file).map_err(|e| Error::Generic(e.to_string()))?;
+        write_atomic(&self.path, &content)
+    }
+
+    fn invalid(&self, e: serde_json::Error) -> Error {
+        Error::Generic(format!("Invalid presets file {}: {}", self.path.display(), e))
+    }
+}
+
+/// Upgrade a stored presets file to the current schema version.
+/// Returns whether any migration ran.
+fn migrate(mut value: Value) -> Result<(Value, bool), Error> {
+    let mut version = value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let migrated = version < PRESETS_SCHEMA_VERSION;
+
+    if version > PRESETS_SCHEMA_VERSION {
+        return Err(Error::Generic(format!(
+            "Presets were saved by a newer LiveSaver (schema {}), this version supports up to {}",
+            version, PRESETS_SCHEMA_VERSION
+        )));
+    }
+
+    while version < PRESETS_SCHEMA_VERSION {
+        value = MIGRATIONS[version as usize](value);
+        version += 1;
+        // Indexing anything but an object panics
+        if !value.is_object() {
+            return Err(Error::Generic("Invalid presets file: expected a list of presets".to_string()));
+        }
+        value["schema_version"] = json!(version);
+    }
+
+    Ok((value, migrated))
+}
+
+/// v0 files are a bare list of presets without a schema version
+fn migrate_v0(value: Value) -> Value {
+    match value {
+        Value::Array(presets) => json!({ "presets": presets }),
+        other => other,
+    }
+}
+
+fn not_found(name: &str) -> Error {
+    Error::Generic(format!("No preset named {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_a_bare_list() {
+        let (value, migrated) = migrate(json!([])).unwrap();
+
+        assert!(migrated);
+        assert_eq!(value, json!({ "schema_version": PRESETS_SCHEMA_VERSION, "presets": [] }));
+    }
+
+    #[test]
+    fn keeps_current_files() {
+        let current = json!({ "schema_version": PRESETS_SCHEMA_VERSION, "presets": [] });
+        let (value, migrated) = migrate(current.clone()).unwrap();
+
+        assert!(!migrated);
+        assert_eq!(value, current);
+    }
+
+    #[test]
+    fn rejects_newer_files() {
+        assert!(migrate(json!({ "schema_version": PRESETS_SCHEMA_VERSION + 1, "presets": [] })).is_err());
+    }
+
+    #[test]
+    fn rejects_values_that_are_not_presets() {
+        for value in [json!("oops"), json!(1), json!(null)] {
+            assert!(migrate(value).is_err());
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::error::{Error, IoContext, Operation};
+use crate::history::History;
+use crate::report::models::{ProjectReport, ProjectStatus, ReportFormat, RunReport};
+use std::fs;
+use std::path::Path;
+
+/// The report of a job in the history
+pub fn find_report(history: &History, job_id: &str) -> Result<RunReport, Error> {
+    history
+        .get(job_id)?
+        .report
+        .ok_or_else(|| Error::Generic(format!("No report for job {}", job_id)))
+}
+
+pub fn write_report(report: &RunReport, format: ReportFormat, path: &Path) -> Result<(), Error> {
+    fs::write(path, render(report, format)?).on(Operation::Write, path)
+}
+
+pub fn render(report: &RunReport, format: ReportFormat) -> Result<String, Error> {
+    match format {
+        ReportFormat::Json => serde_json::to_string_pretty(report).map_err(|e| Error::Generic(e.to_string())),
+        ReportFormat::Csv => Ok(to_csv(report)),
+        ReportFormat::Html => Ok(to_html(report)),
+    }
+}
+
+fn to_csv(report: &RunReport) -> String {
+    let mut csv = String::from(
+        "file_name,status,samples_collected,samples_skipped,samples_missing,samples_mismatched,bytes_copied,duration_ms,conflict,error\n",
+    );
+
+    for p in &report.projects {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&p.file_name),
+            p.status.label(),
+            p.samples_collected,
+            p.samples_skipped,
+            p.samples_missing,
+            p.samples_mismatched,
+            p.bytes_copied,
+            p.duration_ms,
+            conflict_label(p),
+            csv_field(p.error.as_deref().unwrap_or_default()),
+        ));
+    }
+
+    csv
+}
+
+/// How an existing project folder in the mover target was handled
+fn conflict_label(project: &ProjectReport) -> &'static str {
+    project.conflict.as_ref().map(|c| c.policy.label()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Standalone page without external assets so it can be attached to tickets
+fn to_html(report: &RunReport) -> String {
+    let rows: String = report
+        .projects
+        .iter()
+        .map(|p| {
+            format!(
+                "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                p.status.label().replace(' ', "-"),
+                html_escape(&p.file_name),
+                p.status.label(),
+                p.samples_collected,
+                p.samples_skipped,
+                p.samples_missing,
+                p.samples_mismatched,
+                format_bytes(p.bytes_copied),
+                format_duration(p.duration_ms),
+                conflict_label(p),
+                html_escape(p.error.as_deref().unwrap_or_default()),
+            )
+        })
+        .collect();
+
+    let scan_errors: String = if report.scan_errors.is_empty() {
+        String::new()
+    } else {
+        let items: String = report
+            .scan_errors
+            .iter()
+            .map(|e| format!("<li>{}</li>\n", html_escape(&e.message)))
+            .collect();
+        format!("<h2>Could not scan</h2>\n<ul>\n{}</ul>\n", items)
+    };
+
+    let finished_at = report
+        .finished_at
+        .map(|f| f.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>LiveSaver {kind} report {job_id}</title>
+<style>
+body {{ font-family: -apple-system, "Segoe UI", sans-serif; margin: 2rem; color: #1a1a1a; }}
+table {{ border-collapse: collapse; width: 100%; font-size: 0.875rem; }}
+th, td {{ border-bottom: 1px solid #ddd; padding: 0.4rem 0.6rem; text-align: left; }}
+th {{ background: #f4f4f4; }}
+tr.failed td {{ color: #b00020; }}
+tr.skipped td, tr.not-processed td {{ color: #777; }}
+dl {{ display: grid; grid-template-columns: max-content auto; gap: 0.25rem 1rem; }}
+dt {{ font-weight: 600; }}
+</style>
+</head>
+<body>
+<h1>LiveSaver {kind} report</h1>
+<dl>
+<dt>Job</dt><dd>{job_id}</dd>
+<dt>Started</dt><dd>{started_at}</dd>
+<dt>Finished</dt><dd>{finished_at}</dd>
+<dt>Duration</dt><dd>{duration}</dd>
+<dt>Cancelled</dt><dd>{cancelled}</dd>
+<dt>Projects</dt><dd>{processed} processed, {skipped} skipped, {failed} failed, {not_processed} not processed</dd>
+<dt>Copied</dt><dd>{bytes}</dd>
+</dl>
+<table>
+<thead><tr><th>Project</th><th>Status</th><th>Collected</th><th>Skipped</th><th>Missing</th><th>Mismatched</th><th>Copied</th><th>Duration</th><th>Conflict</th><th>Error</th></tr></thead>
+<tbody>
+{rows}</tbody>
+</table>
+{scan_errors}</body>
+</html>
+"#,
+        kind = report.kind.label(),
+        job_id = html_escape(&report.job_id),
+        started_at = report.started_at.format("%Y-%m-%d %H:%M:%S"),
+        finished_at = finished_at,
+        duration = format_duration(report.duration_ms),
+        cancelled = if report.cancelled { "yes" } else { "no" },
+        processed = report.count(ProjectStatus::Processed),
+        skipped = report.count(ProjectStatus::Skipped),
+        failed = report.count(ProjectStatus::Failed),
+        not_processed = report.count(ProjectStatus::NotProcessed),
+        bytes = format_bytes(report.bytes_copied()),
+        rows = rows,
+        scan_errors = scan_errors,
+    )
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn format_duration(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs {
+        0 => format!("{} ms", ms),
+        1..=59 => format!("{}.{} s", secs, (ms % 1000) / 100),
+        _ => format!("{}m {}s", secs / 60, secs % 60),
+    }
+}
@@ -0,0 +1,5 @@
+mod export;
+mod models;
+
+pub use export::*;
+pub use models::*;
@@ -0,0 +1,114 @@
+use crate::jobs::JobKind;
+use crate::utils::{Conflict, ScanError};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Record of a single copify or mover run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RunReport {
+    pub job_id: String,
+    pub kind: JobKind,
+    pub started_at: DateTime<Local>,
+    pub finished_at: Option<DateTime<Local>>,
+    pub duration_ms: u64,
+    pub cancelled: bool,
+    pub projects: Vec<ProjectReport>,
+    // Folders that could not be read while scanning
+    #[serde(default)]
+    pub scan_errors: Vec<ScanError>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectReport {
+    pub file_name: String,
+    pub status: ProjectStatus,
+    pub samples_collected: usize,
+    pub samples_skipped: usize,
+    pub samples_missing: usize,
+    // Samples whose copy did not match after verifying, left uncollected
+    #[serde(default)]
+    pub samples_mismatched: usize,
+    pub bytes_copied: u64,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+    // The project folder already existed in the mover target
+    #[serde(default)]
+    pub conflict: Option<Conflict>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatus {
+    Processed,
+    Skipped,
+    Failed,
+    // Never reached because the job was cancelled
+    NotProcessed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Json,
+    Csv,
+    Html,
+}
+
+impl RunReport {
+    pub fn new(job_id: &str, kind: JobKind) -> Self {
+        RunReport {
+            job_id: job_id.to_string(),
+            kind,
+            started_at: Local::now(),
+            finished_at: None,
+            duration_ms: 0,
+            cancelled: false,
+            projects: Vec::new(),
+            scan_errors: Vec::new(),
+        }
+    }
+
+    pub fn finish(&mut self) {
+        let finished_at = Local::now();
+        self.duration_ms = (finished_at - self.started_at).num_milliseconds().max(0) as u64;
+        self.finished_at = Some(finished_at);
+        self.cancelled = self.count(ProjectStatus::NotProcessed) > 0;
+    }
+
+    pub fn count(&self, status: ProjectStatus) -> usize {
+        self.projects.iter().filter(|p| p.status == status).count()
+    }
+
+    pub fn bytes_copied(&self) -> u64 {
+        self.projects.iter().map(|p| p.bytes_copied).sum()
+    }
+}
+
+impl ProjectReport {
+    pub fn new(file_name: String, status: ProjectStatus) -> Self {
+        ProjectReport {
+            file_name,
+            status,
+            samples_collected: 0,
+            samples_skipped: 0,
+            samples_missing: 0,
+            samples_mismatched: 0,
+            bytes_copied: 0,
+            duration_ms: 0,
+            error: None,
+            conflict: None,
+        }
+    }
+}
+
+impl ProjectStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProjectStatus::Processed => "processed",
+            ProjectStatus::Skipped => "skipped",
+            ProjectStatus::Failed => "failed",
+            ProjectStatus::NotProcessed => "not processed",
+        }
+    }
+}
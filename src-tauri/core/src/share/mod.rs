@@ -0,0 +1,5 @@
+mod models;
+pub(crate) mod share;
+
+pub use models::*;
+pub use share::*;
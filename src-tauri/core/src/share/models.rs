@@ -0,0 +1,29 @@
+use crate::archive::ArchiveFormat;
+use crate::utils::SetInfo;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// What goes into a package for collaborators besides the project itself.
+/// `Backup` folders and `.als.bak` files are always left out.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ShareSettings {
+    // Leave out frozen tracks, they are rendered again when unfrozen
+    #[serde(default)]
+    pub drop_freeze_files: bool,
+    // Add a README listing plugins, Live version, tempo and missing media
+    #[serde(default)]
+    pub readme: bool,
+    // Also pack the package into an archive next to it
+    #[serde(default)]
+    pub archive: Option<ArchiveFormat>,
+}
+
+/// A package written by `share_project`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SharePackage {
+    pub folder: PathBuf,
+    pub archive: Option<PathBuf>,
+    pub sets: Vec<SetInfo>,
+    pub files: usize,
+    pub bytes: u64,
+}
@@ -0,0 +1,149 @@
+use crate::archive::export_archive;
+use crate::jobs::CancelToken;
+use crate::prelude::*;
+use crate::share::models::{SharePackage, ShareSettings};
+use crate::utils::{
+    copy_file, is_backup_folder, read_set_info, CopyOptions, Phase, Reporter, SetInfo, ALS, ALS_BACKUP_EXTENSION,
+    FREEZE_FOLDER, SHARE_README,
+};
+use chrono::Local;
+use std::fs;
+use std::io;
+use std::path::Path;
+use walkdir::{DirEntry, WalkDir};
+
+/// Copy a collected project into `output` as a package for collaborators,
+/// without backups and depending on `settings` without freeze files,
+/// with a README and packed into an archive.
+///
+/// Returns `Error::Cancelled` before the next file once `cancel` is set.
+/// An incomplete package is removed again.
+pub fn share_project(
+    project: &Path,
+    output: &Path,
+    settings: &ShareSettings,
+    cancel: &CancelToken,
+    reporter: &Reporter,
+) -> Result<SharePackage> {
+    let project = project.canonicalize().on(Operation::Read, project)?;
+    let name = project
+        .file_name()
+        .ok_or(Error::Generic(format!("{} is not a project folder", project.display())))?;
+
+    let folder = output.join(name);
+    if folder.exists() {
+        return Err(Error::io(Operation::Create, folder, io::ErrorKind::AlreadyExists.into()));
+    }
+
+    let package = write_package(&project, &folder, settings, cancel, reporter);
+    if package.is_err() {
+        let _ = fs::remove_dir_all(&folder);
+    }
+    let mut package = package?;
+
+    if let Some(format) = settings.archive {
+        let archive = output.join(format!("{}.{}", name.to_string_lossy(), format.extension()));
+        export_archive(&folder, &archive, format, cancel, reporter)?;
+        package.archive = Some(archive);
+    }
+
+    Ok(package)
+}
+
+fn write_package(
+    project: &Path,
+    folder: &Path,
+    settings: &ShareSettings,
+    cancel: &CancelToken,
+    reporter: &Reporter,
+) -> Result<SharePackage> {
+    reporter.phase(Phase::Scanning, Some(project));
+    let files: Vec<DirEntry> = WalkDir::new(project)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !is_left_out(entry, project, settings))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .collect();
+
+    let bytes = files.iter().filter_map(|e| e.metadata().ok()).map(|m| m.len()).sum();
+    reporter.phase(Phase::Copying, Some(folder));
+    reporter.add_total(bytes);
+
+    let mut sets = Vec::new();
+    for entry in &files {
+        if cancel.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        let relative = entry.path().strip_prefix(project).unwrap_or(entry.path());
+        let destination = folder.join(relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).on(Operation::Create, parent)?;
+        }
+        copy_file(entry.path(), &destination, &CopyOptions::default())?;
+        reporter.advance(entry.metadata().map(|m| m.len()).unwrap_or_default());
+
+        if destination.extension().is_some_and(|ext| ext == ALS) {
+            sets.push(read_set_info(&destination)?);
+        }
+    }
+
+    if settings.readme {
+        let readme = folder.join(SHARE_README);
+        fs::write(&readme, render_readme(project, folder, &sets)).on(Operation::Write, &readme)?;
+    }
+
+    Ok(SharePackage {
+        folder: folder.to_path_buf(),
+        archive: None,
+        sets,
+        files: files.len(),
+        bytes,
+    })
+}
+
+/// Backups never leave the studio, freeze files only when asked to
+fn is_left_out(entry: &DirEntry, project: &Path, settings: &ShareSettings) -> bool {
+    let path = entry.path();
+    if entry.file_type().is_dir() {
+        return path.file_name().is_some_and(|name| name == "Backup")
+            || (settings.drop_freeze_files && path.strip_prefix(project).is_ok_and(|p| p == Path::new(FREEZE_FOLDER)));
+    }
+
+    is_backup_folder(path) || path.to_string_lossy().ends_with(ALS_BACKUP_EXTENSION)
+}
+
+/// Plain text, so it opens anywhere without LiveSaver
+fn render_readme(project: &Path, folder: &Path, sets: &[SetInfo]) -> String {
+    let name = project.file_name().unwrap_or_default().to_string_lossy();
+    let mut readme = format!("{}\nShared on {}\n", name, Local::now().format("%Y-%m-%d"));
+
+    for set in sets {
+        let relative = set.path.strip_prefix(folder).unwrap_or(&set.path);
+        readme.push_str(&format!("\n{}\n", relative.display()));
+        readme.push_str(&format!("  Live version: {}\n", set.live_version.as_deref().unwrap_or("unknown")));
+        match set.tempo {
+            Some(tempo) => readme.push_str(&format!("  Tempo: {} BPM\n", tempo)),
+            None => readme.push_str("  Tempo: unknown\n"),
+        }
+
+        push_list(&mut readme, "Plugins", set.plugins.iter().map(String::as_str));
+        push_list(&mut readme, "Missing media", set.missing_samples().map(|s| s.path.as_str()));
+    }
+
+    readme
+}
+
+fn push_list<'a>(readme: &mut String, title: &str, items: impl Iterator<Item = &'a str>) {
+    let items: Vec<&str> = items.collect();
+    if items.is_empty() {
+        readme.push_str(&format!("  {}: none\n", title));
+        return;
+    }
+
+    readme.push_str(&format!("  {}:\n", title));
+    for item in items {
+        readme.push_str(&format!("    - {}\n", item));
+    }
+}
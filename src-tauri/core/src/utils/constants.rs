@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+// Same as the identifier in tauri.conf.json, names the app data folder
+pub static APP_IDENTIFIER: &str = "app.livesaver";
+// Finished jobs kept in the history, older ones are dropped with their reports
+pub static HISTORY_LIMIT: usize = 200;
+pub static SAMPLES_IMPORTED: &str = "Samples/Imported/";
+pub static ALS: &str = "als";
+pub static ALS_EXTENSION: &str = concat!(".", "als");
+pub static ALS_BACKUP_EXTENSION: &str = concat!(".", "als", ".bak");
+pub static XML: &str = "xml";
+pub static XML_EXTENSION: &str = concat!(".", "xml");
+// Per-folder file with exclusion patterns, honoured when scanning for sets
+pub static IGNORE_FILE: &str = ".livesaverignore";
+// Extra copies of a file whose checksum did not match its source
+pub static VERIFY_RETRIES: usize = 2;
+// Modification times closer than this are equal, FAT and exFAT drives store them in 2 second steps
+pub static MTIME_TOLERANCE: Duration = Duration::from_secs(2);
+// Describes a project archive, at the root of the archive
+pub static MANIFEST_FILE: &str = "livesaver-manifest.json";
+pub static MANIFEST_VERSION: u32 = 1;
+// Frozen tracks, relative to the project folder
+pub static FREEZE_FOLDER: &str = "Samples/Processed/Freeze";
+pub static SHARE_README: &str = "README.txt";
+pub static PROJECT_INFO: &str = "Ableton Project Info";
+// Folders created by the operating system that never hold projects
+pub static SYSTEM_FOLDERS: [&str; 6] = [
+    "$RECYCLE.BIN",
+    "System Volume Information",
+    "lost+found",
+    "__MACOSX",
+    ".Trashes",
+    ".Spotlight-V100",
+];
@@ -0,0 +1,31 @@
+use crate::utils::Progress;
+
+pub fn on_success(job_id: &str, file_name: String, progress: usize) -> Progress {
+    make_progress(job_id, file_name, progress, false, false, "".to_string())
+}
+
+pub fn on_error(job_id: &str, file_name: String, progress: usize, error_msg: String) -> Progress {
+    make_progress(job_id, file_name, progress, true, false, error_msg)
+}
+
+pub fn on_skip(job_id: &str, file_name: String, progress: usize) -> Progress {
+    make_progress(job_id, file_name, progress, false, true, "Project was skipped.".to_string())
+}
+
+pub fn make_progress(
+    job_id: &str,
+    file_name: String,
+    progress: usize,
+    is_error: bool,
+    is_skipped: bool,
+    error_msg: String,
+) -> Progress {
+    Progress {
+        job_id: job_id.to_string(),
+        is_error,
+        is_skipped,
+        error_msg,
+        progress,
+        file_name,
+    }
+}
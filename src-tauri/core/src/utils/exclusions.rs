@@ -0,0 +1,194 @@
+use crate::prelude::*;
+use crate::utils::{is_backup_folder, ALS, IGNORE_FILE};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Gitignore-style patterns of sets to skip, relative to the scanned
+/// folder. `Demo` matches a file or folder named exactly `Demo` at any
+/// depth, `Live/*.als` only below `Live` in the scanned folder and
+/// `!Demo/Keep.als` brings a set back.
+///
+/// A pattern that is exactly a set's file name always matches it, so
+/// names picked from a project list work even with glob characters like
+/// `Song [v2].als`.
+///
+/// Sets excluded by a `.livesaverignore` file are excluded too, also
+/// when they were not found by scanning, e.g. a set saved while watching.
+pub struct Exclusions {
+    root: PathBuf,
+    matcher: Gitignore,
+    names: Vec<String>,
+}
+
+impl Exclusions {
+    pub fn new(root: &Path, patterns: &[String]) -> Result<Self> {
+        let mut builder = GitignoreBuilder::new(root);
+        for pattern in patterns {
+            builder
+                .add_line(None, pattern)
+                .map_err(|e| Error::Generic(format!("Invalid exclude pattern {}: {}", pattern, e)))?;
+        }
+        let matcher = builder
+            .build()
+            .map_err(|e| Error::Generic(format!("Invalid exclude patterns: {}", e)))?;
+
+        Ok(Exclusions {
+            root: root.to_path_buf(),
+            matcher,
+            names: patterns.to_vec(),
+        })
+    }
+
+    pub fn is_excluded(&self, file_path: &Path) -> bool {
+        let by_name = file_path
+            .file_name()
+            .is_some_and(|name| self.names.iter().any(|n| name == n.as_str()));
+
+        // Paths outside the scanned folder can only match by name
+        let by_pattern = file_path
+            .strip_prefix(&self.root)
+            .is_ok_and(|relative| self.matcher.matched_path_or_any_parents(relative, false).is_ignore());
+
+        by_name || by_pattern || is_ignored(file_path)
+    }
+
+    /// Excluded sets anywhere below `folder`, including those scanning
+    /// already skipped because of a `.livesaverignore` file
+    pub fn excluded_below(&self, folder: &Path) -> Vec<PathBuf> {
+        WalkDir::new(folder)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == ALS) && !is_backup_folder(path))
+            .filter(|path| self.is_excluded(path))
+            .collect()
+    }
+}
+
+/// Whether a `.livesaverignore` file in a folder above `file_path`
+/// excludes it, the same way `find_by_extension` skips it while scanning:
+/// a set in an ignored folder is never reached, otherwise the ignore
+/// file closest to the set wins.
+pub fn is_ignored(file_path: &Path) -> bool {
+    let Some(parent) = file_path.parent() else {
+        return false;
+    };
+
+    // Closest folder first
+    let matchers: Vec<(&Path, Gitignore)> = parent
+        .ancestors()
+        .filter_map(|folder| {
+            let ignore_file = folder.join(IGNORE_FILE);
+            if !ignore_file.is_file() {
+                return None;
+            }
+            let (matcher, error) = Gitignore::new(&ignore_file);
+            if let Some(e) = error {
+                log::warn!("Invalid line in {}: {}", ignore_file.display(), e);
+            }
+            Some((folder, matcher))
+        })
+        .collect();
+
+    let ignored = |path: &Path, is_dir: bool| {
+        matchers
+            .iter()
+            .filter(|(folder, _)| path != *folder && path.starts_with(folder))
+            .map(|(_, matcher)| matcher.matched(path, is_dir))
+            .find(|m| !m.is_none())
+            .is_some_and(|m| matches!(m, Match::Ignore(_)))
+    };
+
+    let mut folders: Vec<&Path> = parent.ancestors().collect();
+    folders.reverse();
+    folders.iter().any(|folder| ignored(folder, true)) || ignored(file_path, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{find_by_extension, DiscoveryOptions};
+    use std::fs;
+
+    fn exclusions(patterns: &[&str]) -> Exclusions {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        Exclusions::new(Path::new("/projects"), &patterns).unwrap()
+    }
+
+    #[test]
+    fn matches_whole_names_only() {
+        let exclusions = exclusions(&["Demo"]);
+
+        assert!(exclusions.is_excluded(Path::new("/projects/Demo/Song.als")));
+        assert!(exclusions.is_excluded(Path::new("/projects/Clients/Demo/Song.als")));
+        assert!(!exclusions.is_excluded(Path::new("/projects/Demolition Project/Song.als")));
+        assert!(!exclusions.is_excluded(Path::new("/projects/My Demo/Song.als")));
+    }
+
+    #[test]
+    fn anchors_paths_to_the_scanned_folder() {
+        let exclusions = exclusions(&["Live/*.als"]);
+
+        assert!(exclusions.is_excluded(Path::new("/projects/Live/Song.als")));
+        assert!(!exclusions.is_excluded(Path::new("/projects/Other/Live/Song.als")));
+    }
+
+    #[test]
+    fn brings_back_negated_sets() {
+        let exclusions = exclusions(&["Demo/*", "!Demo/Keep.als"]);
+
+        assert!(exclusions.is_excluded(Path::new("/projects/Demo/Song.als")));
+        assert!(!exclusions.is_excluded(Path::new("/projects/Demo/Keep.als")));
+    }
+
+    #[test]
+    fn reads_ignore_files_above_the_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let song = dir.path().join("Clients/Song");
+        let demos = dir.path().join("Demos");
+        fs::create_dir_all(&song).unwrap();
+        fs::create_dir_all(&demos).unwrap();
+        fs::write(dir.path().join(IGNORE_FILE), "Draft*.als\nDemos/\n").unwrap();
+        // A closer ignore file wins, but never inside an ignored folder
+        fs::write(song.join(IGNORE_FILE), "!Draft Keep.als\n").unwrap();
+        fs::write(demos.join(IGNORE_FILE), "!*.als\n").unwrap();
+
+        assert!(is_ignored(&dir.path().join("Draft 1.als")));
+        assert!(is_ignored(&song.join("Draft 2.als")));
+        assert!(!is_ignored(&song.join("Draft Keep.als")));
+        assert!(!is_ignored(&song.join("Song.als")));
+        assert!(is_ignored(&demos.join("Demo.als")));
+
+        // Scanning skips exactly the same sets
+        let sets = [
+            dir.path().join("Draft 1.als"),
+            song.join("Draft 2.als"),
+            song.join("Draft Keep.als"),
+            song.join("Song.als"),
+            demos.join("Demo.als"),
+        ];
+        for set in &sets {
+            fs::write(set, "").unwrap();
+        }
+        let found = find_by_extension(dir.path().to_str().unwrap(), ALS, &DiscoveryOptions::default()).files;
+        for set in &sets {
+            assert_eq!(found.contains(set), !is_ignored(set), "{}", set.display());
+        }
+
+        // Applies to sets that were never scanned, whatever the exclude patterns
+        let exclusions = Exclusions::new(&song, &[]).unwrap();
+        assert!(exclusions.is_excluded(&song.join("Draft 2.als")));
+    }
+
+    #[test]
+    fn matches_exact_file_names_with_glob_characters() {
+        let exclusions = exclusions(&["Song [v2].als"]);
+
+        assert!(exclusions.is_excluded(Path::new("/projects/Song/Song [v2].als")));
+        assert!(!exclusions.is_excluded(Path::new("/projects/Song/Song [v3].als")));
+        // Outside the scanned folder only the exact name matches
+        assert!(exclusions.is_excluded(Path::new("/elsewhere/Song [v2].als")));
+    }
+}
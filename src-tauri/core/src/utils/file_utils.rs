@@ -0,0 +1,809 @@
+use crate::jobs::CancelToken;
+use crate::prelude::*;
+use crate::utils::{
+    Conflict, ConflictPolicy, CopyOptions, Discovery, FailedMove, Incremental, DiscoveryOptions, MovedFile, Moves, Phase, PlannedMove, ProjectFolder,
+    Reporter, TargetTemplate, ScanError, APP_IDENTIFIER, IGNORE_FILE, MTIME_TOLERANCE, PROJECT_INFO, SYSTEM_FOLDERS, VERIFY_RETRIES,
+};
+use flate2::write::{GzDecoder, GzEncoder};
+use filetime::FileTime;
+use flate2::Compression;
+use pathdiff::diff_paths;
+use std::fs;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read};
+use std::path::{Path, PathBuf};
+use ignore::WalkBuilder;
+use walkdir::WalkDir;
+
+/// Where a sample ends up inside the project folder
+pub fn sample_destination(sample: &str, project_root: &Path) -> Result<PathBuf> {
+    let filename = Path::new(sample)
+        .file_name()
+        .ok_or(Error::CopifyFailed("Invalid sample path: no filename".to_string()))?;
+
+    Ok(project_root.join("Samples").join("Imported").join(filename))
+}
+
+/// Copy a sample into the project folder and return its new path
+/// together with the number of bytes copied
+pub fn copy_sample(sample: &str, project_root: &Path, options: &CopyOptions) -> Result<(String, u64)> {
+    let dest_file = sample_destination(sample, project_root)?;
+    let destination = dest_file.parent().unwrap_or(project_root);
+
+    // Already collected by an earlier run, copying a file onto itself would truncate it
+    if is_same_file(Path::new(sample), &dest_file) {
+        return Ok((dest_file.to_string_lossy().into_owned(), 0));
+    }
+
+    // Try to create the destination folder
+    fs::create_dir_all(destination).on(Operation::Create, destination)?;
+
+    // If the file already exists, optionally overwrite or skip
+    let bytes = copy_file(Path::new(sample), &dest_file, options)?;
+
+    Ok((dest_file.to_string_lossy().into_owned(), bytes))
+}
+
+/// Copy a single file and return the number of bytes copied, zero when
+/// `options.incremental` found it up to date at `dst`.
+///
+/// With `options.verify` the copy is compared with the source by
+/// checksum and copied again up to `VERIFY_RETRIES` times. A copy that
+/// still differs is removed and `Error::ChecksumMismatch` is returned.
+pub fn copy_file(src: &Path, dst: &Path, options: &CopyOptions) -> Result<u64> {
+    if is_up_to_date(src, dst, options.incremental) {
+        return Ok(0);
+    }
+
+    let mut attempt = 0;
+
+    let bytes = loop {
+        let bytes = fs::copy(src, dst).between(Operation::Copy, src, dst)?;
+        if !options.verify || file_hash(src).on(Operation::Read, src)? == file_hash(dst).on(Operation::Read, dst)? {
+            break bytes;
+        }
+
+        log::warn!("Copy of {} does not match its source (attempt {})", src.display(), attempt + 1);
+        if attempt == VERIFY_RETRIES {
+            let _ = fs::remove_file(dst);
+            return Err(Error::ChecksumMismatch {
+                original: src.to_path_buf(),
+                copy: dst.to_path_buf(),
+            });
+        }
+        attempt += 1;
+    };
+
+    if options.preserve_metadata {
+        preserve_metadata(src, dst);
+    }
+
+    Ok(bytes)
+}
+
+fn is_up_to_date(src: &Path, dst: &Path, incremental: Incremental) -> bool {
+    let (Ok(source), Ok(copy)) = (fs::metadata(src), fs::metadata(dst)) else {
+        return false;
+    };
+    if source.len() != copy.len() {
+        return false;
+    }
+
+    match incremental {
+        Incremental::Off => false,
+        Incremental::SizeAndTime => match (source.modified(), copy.modified()) {
+            (Ok(a), Ok(b)) => a.duration_since(b).or_else(|_| b.duration_since(a)).is_ok_and(|d| d < MTIME_TOLERANCE),
+            _ => false,
+        },
+        Incremental::Checksum => matches!((file_hash(src), file_hash(dst)), (Ok(a), Ok(b)) if a == b),
+    }
+}
+
+pub(crate) fn file_hash(path: &Path) -> io::Result<blake3::Hash> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update_reader(File::open(path)?)?;
+    Ok(hasher.finalize())
+}
+
+/// Give a copy the timestamps, permissions and extended attributes of
+/// its source. The copy itself is fine without them, so failures are
+/// only logged.
+///
+/// Extended attributes are copied one by one, drives like exFAT or SMB
+/// shares refuse them but still keep the permissions and timestamps.
+fn preserve_metadata(src: &Path, dst: &Path) {
+    #[cfg(unix)]
+    copy_xattrs(src, dst);
+
+    // Last, a read-only copy cannot get extended attributes anymore
+    if let Err(e) = copy_metadata(src, dst) {
+        log::warn!("Could not keep the metadata of {}: {}", src.display(), e);
+    }
+}
+
+#[cfg(unix)]
+fn copy_xattrs(src: &Path, dst: &Path) {
+    if !xattr::SUPPORTED_PLATFORM {
+        return;
+    }
+
+    let names = match xattr::list(src) {
+        Ok(names) => names,
+        Err(e) => {
+            log::warn!("Could not read the extended attributes of {}: {}", src.display(), e);
+            return;
+        }
+    };
+
+    for name in names {
+        let result = xattr::get(src, &name).and_then(|value| match value {
+            Some(value) => xattr::set(dst, &name, &value),
+            None => Ok(()),
+        });
+        if let Err(e) = result {
+            log::warn!(
+                "Could not keep the extended attribute {} of {}: {}",
+                name.to_string_lossy(),
+                src.display(),
+                e
+            );
+        }
+    }
+}
+
+fn copy_metadata(src: &Path, dst: &Path) -> io::Result<()> {
+    let metadata = fs::metadata(src)?;
+
+    fs::set_permissions(dst, metadata.permissions())?;
+    filetime::set_file_times(
+        dst,
+        FileTime::from_last_access_time(&metadata),
+        FileTime::from_last_modification_time(&metadata),
+    )
+}
+
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+pub fn compress(input: &Path, output: &Path) -> Result<()> {
+    let input_file = File::open(input).on(Operation::Read, input)?;
+    let buffered_reader = BufReader::new(input_file);
+
+    let output_file = File::create(output).on(Operation::Create, output)?;
+    let buffered_writer = BufWriter::new(output_file);
+
+    let mut encoder = GzEncoder::new(buffered_writer, Compression::default());
+    io::copy(&mut buffered_reader.take(u64::MAX), &mut encoder).on(Operation::Compress, input)?;
+    encoder.finish().on(Operation::Write, output)?;
+
+    Ok(())
+}
+
+pub fn decompress(input: &Path, output: &Path) -> Result<()> {
+    // Open the compressed input file
+    let input_file = File::open(input).on(Operation::Read, input)?;
+    let buffered_reader = BufReader::new(input_file);
+
+    // Create the output file
+    let output_file = File::create(output).on(Operation::Create, output)?;
+    let buffered_writer = BufWriter::new(output_file);
+
+    // Create a GzDecoder for decompression
+    let mut decoder = GzDecoder::new(buffered_writer);
+
+    // Copy the decompressed data to the output file
+    io::copy(&mut buffered_reader.take(u64::MAX), &mut decoder).on(Operation::Decompress, input)?;
+    decoder.try_finish().on(Operation::Write, output)?;
+
+    Ok(())
+}
+
+/// Write `content` to `path` through a temporary file next to it.
+///
+/// The rename replaces the file in one step, so a crash while writing
+/// leaves the previous content in place instead of half a file. Missing
+/// parent folders are created.
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).on(Operation::Create, dir)?;
+    }
+
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    fs::write(&temp, content).on(Operation::Write, &temp)?;
+    fs::rename(&temp, path).between(Operation::Move, &temp, path)
+}
+
+/// Find files by a specific file type
+///
+/// Default ignore Ableton project files that
+/// are in the "Backup" folder. Files and folders matched by a
+/// `.livesaverignore` file are skipped as well.
+///
+/// Folders that cannot be read are returned as errors next to the
+/// files that were found, the walk continues past them.
+pub fn find_by_extension(folder: &str, ext: &str, options: &DiscoveryOptions) -> Discovery {
+    let mut discovery = Discovery::default();
+
+    let path = Path::new(folder);
+    if !path.is_dir() {
+        discovery.errors.push(ScanError {
+            path: Some(path.to_path_buf()),
+            message: "Provided path is not a valid directory".to_string(),
+        });
+        return discovery;
+    }
+
+    // Compared relative to the scanned folder so differently spelled
+    // paths to the same folder still match
+    let root = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let skip_folders: Vec<PathBuf> = options
+        .skip_folders
+        .iter()
+        .filter_map(|f| f.canonicalize().ok()?.strip_prefix(&root).ok().map(Path::to_path_buf))
+        .collect();
+
+    let walk_root = path.to_path_buf();
+    let skip_hidden = options.skip_hidden;
+    let skip_project_info = options.skip_project_info;
+    let walker = WalkBuilder::new(folder)
+        .standard_filters(false)
+        .parents(true)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .hidden(skip_hidden)
+        .max_depth(options.max_depth)
+        .follow_links(options.follow_symlinks)
+        .filter_entry(move |entry| {
+            if !entry.file_type().is_some_and(|t| t.is_dir()) {
+                return true;
+            }
+            let name = entry.file_name().to_string_lossy();
+            let relative = entry.path().strip_prefix(&walk_root).ok();
+
+            !(skip_hidden && SYSTEM_FOLDERS.contains(&name.as_ref())
+                || skip_project_info && name == PROJECT_INFO
+                || relative.is_some_and(|r| skip_folders.iter().any(|f| f == r)))
+        })
+        .build();
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let error = scan_error(e);
+                log::warn!("Skipped while scanning {}: {}", folder, error.message);
+                discovery.errors.push(error);
+                continue;
+            }
+        };
+
+        let entry_path = entry.path();
+        if entry_path.is_file() {
+            if let Some(extension) = entry_path.extension() {
+                if extension == ext {
+                    let file_path = entry_path.to_path_buf();
+                    if !is_backup_folder(&file_path) {
+                        discovery.files.push(entry_path.to_path_buf())
+                    }
+                }
+            }
+        }
+    }
+
+    discovery
+}
+
+fn scan_error(error: ignore::Error) -> ScanError {
+    let path = match &error {
+        ignore::Error::WithPath { path, .. } => Some(path.clone()),
+        ignore::Error::Loop { child, .. } => Some(child.clone()),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => match err.as_ref() {
+            ignore::Error::WithPath { path, .. } => Some(path.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    ScanError {
+        path,
+        message: error.to_string(),
+    }
+}
+
+pub fn get_last_segment(path: &str) -> &str {
+    path.split("/").last().unwrap()
+}
+
+pub fn absolute_path_from_base(base_folder: &Path, relative_path: &Path) -> io::Result<PathBuf> {
+    base_folder.join(relative_path).canonicalize()
+}
+
+pub fn find_relative_path(base_folder: &str, absolute_path: &str) -> String {
+    let base_folder = Path::new(base_folder);
+    let absolute_path = Path::new(absolute_path);
+
+    // Calculate relative path, handling error cases
+    match diff_paths(absolute_path, base_folder) {
+        Some(relative_path) => {
+            // Normalize path separators to forward slashes
+            let relative_path = relative_path
+                .to_string_lossy()
+                .replace(std::path::MAIN_SEPARATOR, "/");
+            relative_path
+        }
+        None => {
+            // Return the absolute path if relative path calculation fails
+            absolute_path.to_string_lossy().to_string()
+        }
+    }
+}
+
+/// Group project files by the project folder they belong to.
+///
+/// A project folder inside another project folder is moved along with
+/// it, so its files are added to the outer folder.
+pub fn project_folders(files: &[PathBuf], scanned_folder: &Path) -> Vec<ProjectFolder> {
+    let mut roots: Vec<PathBuf> = files.iter().filter_map(|f| project_root(f, scanned_folder)).collect();
+    roots.sort();
+    roots.dedup();
+
+    let outer: Vec<PathBuf> = roots
+        .iter()
+        .filter(|root| !roots.iter().any(|other| other != *root && root.starts_with(other)))
+        .cloned()
+        .collect();
+
+    outer
+        .into_iter()
+        .map(|root| ProjectFolder {
+            sets: files.iter().filter(|f| f.starts_with(&root)).cloned().collect(),
+            root,
+        })
+        .collect()
+}
+
+/// The project folder of a project file: the closest folder holding an
+/// `Ableton Project Info` folder, e.g. `Song` for `Song/Versions/v3.als`.
+///
+/// The search does not go above `scanned_folder`. Without a project info
+/// folder the project file's own folder is used.
+pub fn project_root(file_path: &Path, scanned_folder: &Path) -> Option<PathBuf> {
+    let parent = file_path.parent()?;
+
+    for folder in parent.ancestors() {
+        if folder.join(PROJECT_INFO).is_dir() {
+            return Some(folder.to_path_buf());
+        }
+        if folder == scanned_folder || !folder.starts_with(scanned_folder) {
+            break;
+        }
+    }
+
+    Some(parent.to_path_buf())
+}
+
+/// Decide where every project folder goes in `target_folder`, laid out
+/// by `template`, before anything is moved. Folders whose path already
+/// exists in the target, or is used by an earlier folder of the same
+/// job, are conflicts and handled by `policy`.
+///
+/// With `mirror` the target is updated in place from an earlier run, so
+/// only folders of the same job conflict.
+///
+/// Returns an error listing every conflict when `policy` is `Fail`.
+pub fn plan_moves(
+    folders: Vec<ProjectFolder>,
+    target_folder: &str,
+    template: &TargetTemplate,
+    policy: ConflictPolicy,
+    mirror: bool,
+) -> Result<Vec<PlannedMove>> {
+    let target_base = Path::new(target_folder);
+    let mut planned: Vec<PlannedMove> = Vec::new();
+    // Conflicts with the target on disk and with other folders of this job
+    let mut existing = Vec::new();
+    let mut duplicates = Vec::new();
+
+    for folder in folders {
+        if folder.root.file_name().is_none() {
+            continue;
+        }
+
+        let target = target_base.join(template.render(&folder));
+        let folder_name = target.file_name().unwrap_or_default().to_os_string();
+        let taken = |path: &Path| (!mirror && path.exists()) || planned.iter().any(|p| p.target == path);
+
+        if !taken(&target) {
+            planned.push(PlannedMove { folder, target, conflict: None });
+            continue;
+        }
+
+        if !mirror && target.exists() {
+            existing.push(target.display().to_string());
+        } else {
+            duplicates.push(target.display().to_string());
+        }
+
+        let conflict = Some(Conflict { existing: target.clone(), policy });
+        let target = match policy {
+            ConflictPolicy::Rename => (2..)
+                .map(|n| target.with_file_name(format!("{} ({})", folder_name.to_string_lossy(), n)))
+                .find(|path| !taken(path))
+                .unwrap(),
+            _ => target,
+        };
+        planned.push(PlannedMove { folder, target, conflict });
+    }
+
+    if policy == ConflictPolicy::Fail && !(existing.is_empty() && duplicates.is_empty()) {
+        let mut reasons = Vec::new();
+        if !existing.is_empty() {
+            reasons.push(format!("Project folders already exist in the target: {}", existing.join(", ")));
+        }
+        if !duplicates.is_empty() {
+            reasons.push(format!(
+                "Several project folders of this job go to the same path: {}",
+                duplicates.join(", ")
+            ));
+        }
+        return Err(Error::MoverFailed(reasons.join(". ")));
+    }
+
+    Ok(planned)
+}
+
+/// Move or copy every planned project folder once and return each
+/// project file at its new location. Folders skipped because of a
+/// conflict are left out.
+///
+/// A folder that fails is returned with its error and the next folder
+/// is moved. Stops before the next folder once `cancel` is set, so only
+/// the files that were actually moved are returned.
+pub fn move_or_copy_files(
+    plan: Vec<PlannedMove>,
+    move_files: bool,
+    options: &CopyOptions,
+    cancel: &CancelToken,
+    reporter: &Reporter,
+) -> Moves {
+    let mut moves = Moves::default();
+    let plan: Vec<PlannedMove> = plan
+        .into_iter()
+        .filter(|p| p.conflict.as_ref().is_none_or(|c| c.policy != ConflictPolicy::Skip))
+        .collect();
+
+    reporter.phase(Phase::Scanning, None);
+    for planned in &plan {
+        reporter.add_total(dir_size(&planned.folder.root));
+    }
+
+    reporter.phase(Phase::Moving, None);
+
+    for planned in plan {
+        if cancel.is_cancelled() {
+            break;
+        }
+
+        let bytes_copied = match move_folder(&planned, move_files, options, reporter) {
+            Ok(bytes_copied) => bytes_copied,
+            Err(e) => {
+                log::error!("Could not move {}: {}", planned.folder.root.display(), e);
+                moves.failed.push(FailedMove {
+                    folder: planned.folder,
+                    error: e.to_string(),
+                    conflict: planned.conflict,
+                });
+                continue;
+            }
+        };
+
+        let PlannedMove { folder, target, conflict } = planned;
+        for (index, set) in folder.sets.iter().enumerate() {
+            let relative = set.strip_prefix(&folder.root).unwrap_or(set);
+            moves.moved.push(MovedFile {
+                source: set.clone(),
+                file_path: target.join(relative),
+                // Count the folder once, not once per project file in it
+                bytes_copied: if index == 0 { bytes_copied } else { 0 },
+                conflict: conflict.clone(),
+            });
+        }
+    }
+
+    moves
+}
+
+/// Move or copy a single planned folder and return the number of bytes copied
+fn move_folder(planned: &PlannedMove, move_files: bool, options: &CopyOptions, reporter: &Reporter) -> Result<u64> {
+    let source_folder = planned.folder.root.as_path();
+    let target = planned.target.as_path();
+    let merge = planned.conflict.as_ref().map(|c| c.policy);
+
+    // A template can place the folder several levels deep
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).on(Operation::Create, parent)?;
+    }
+
+    match merge {
+        Some(ConflictPolicy::Merge) | Some(ConflictPolicy::Overwrite) => {
+            let keep_newer = merge == Some(ConflictPolicy::Merge);
+            let copied = merge_dir(source_folder, target, keep_newer, options, reporter)?;
+            if move_files {
+                reporter.phase(Phase::Verifying, Some(target));
+                verify_copy(source_folder, target, keep_newer, options)?;
+                fs::remove_dir_all(source_folder).on(Operation::Remove, source_folder)?;
+            }
+            Ok(copied)
+        }
+        _ if move_files => move_dir(source_folder, target, options, reporter),
+        _ => copy_dir_all(source_folder, target, options, reporter), // custom recursive copy
+    }
+}
+
+/// Copy a folder into an existing folder and return the number of bytes
+/// copied. With `keep_newer` a file that exists in both is only replaced
+/// when the source is newer.
+fn merge_dir(src: &Path, dst: &Path, keep_newer: bool, options: &CopyOptions, reporter: &Reporter) -> Result<u64> {
+    let mut copied = 0;
+    let created = !dst.exists();
+
+    if created {
+        fs::create_dir_all(dst).on(Operation::Create, dst)?;
+    }
+
+    for entry in fs::read_dir(src).on(Operation::List, src)? {
+        let entry = entry.on(Operation::List, src)?;
+        let entry_path = entry.path();
+        let dest_path = dst.join(entry.file_name());
+
+        if entry_path.is_dir() {
+            copied += merge_dir(&entry_path, &dest_path, keep_newer, options, reporter)?;
+            continue;
+        }
+
+        let size = entry.metadata().map(|m| m.len()).unwrap_or_default();
+        if !(keep_newer && dest_path.exists() && !is_newer(&entry_path, &dest_path)) {
+            copied += copy_file(&entry_path, &dest_path, options)?;
+        }
+        reporter.advance(size);
+    }
+
+    // A folder that existed before keeps its own timestamps
+    if created && options.preserve_metadata {
+        preserve_metadata(src, dst);
+    }
+
+    Ok(copied)
+}
+
+fn is_newer(file: &Path, than: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    modified(file) > modified(than)
+}
+
+/// Move a folder and return the number of bytes copied, zero when it
+/// could be renamed.
+///
+/// Renaming fails when the target is on another drive, then the folder
+/// is copied, the copy is verified and only then the source is removed.
+fn move_dir(src: &Path, dst: &Path, options: &CopyOptions, reporter: &Reporter) -> Result<u64> {
+    let size = dir_size(src);
+    let existed = dst.exists();
+
+    match fs::rename(src, dst) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {}
+        result => {
+            result.between(Operation::Move, src, dst)?;
+            reporter.advance(size);
+            return Ok(0);
+        }
+    }
+
+    let copied = copy_dir_all(src, dst, options, reporter).and_then(|copied| {
+        reporter.phase(Phase::Verifying, Some(dst));
+        verify_copy(src, dst, false, options)?;
+        Ok(copied)
+    });
+
+    match copied {
+        Ok(copied) => {
+            fs::remove_dir_all(src).on(Operation::Remove, src)?;
+            Ok(copied)
+        }
+        Err(e) => {
+            // The source is untouched, drop the incomplete copy
+            if !existed {
+                let _ = fs::remove_dir_all(dst);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Check that every file below `src` exists below `dst` with the same
+/// size and content before the source is removed.
+///
+/// With `options.verify` every copy was already compared by checksum
+/// while copying, then only the sizes are compared here. With
+/// `keep_newer` files `merge_dir` left alone because the target was not
+/// older are accepted as they are.
+fn verify_copy(src: &Path, dst: &Path, keep_newer: bool, options: &CopyOptions) -> Result<()> {
+    for entry in WalkDir::new(src) {
+        let entry = entry.map_err(|e| Error::MoverFailed(format!("Could not verify {}: {}", src.display(), e)))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(src).unwrap_or(entry.path());
+        let copy = dst.join(relative);
+        if keep_newer && copy.exists() && !is_newer(entry.path(), &copy) {
+            continue;
+        }
+
+        let expected = entry.metadata().map(|m| m.len()).ok();
+        let actual = fs::metadata(&copy).map(|m| m.len()).ok();
+
+        let matches = expected.is_some()
+            && expected == actual
+            && (options.verify || matches!((file_hash(entry.path()), file_hash(&copy)), (Ok(a), Ok(b)) if a == b));
+
+        if !matches {
+            return Err(Error::MoverFailed(format!(
+                "Copy of {} does not match the source, the source was kept",
+                entry.path().display()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Recursively copy a folder and return the number of bytes copied
+fn copy_dir_all(src: &Path, dst: &Path, options: &CopyOptions, reporter: &Reporter) -> Result<u64> {
+    let mut copied = 0;
+
+    if !dst.exists() {
+        fs::create_dir_all(dst).on(Operation::Create, dst)?;
+    }
+
+    for entry in fs::read_dir(src).on(Operation::List, src)? {
+        let entry = entry.on(Operation::List, src)?;
+        let entry_path = entry.path();
+        let dest_path = dst.join(entry.file_name());
+
+        if entry_path.is_dir() {
+            copied += copy_dir_all(&entry_path, &dest_path, options, reporter)?;
+        } else {
+            // Files that were up to date count as done too
+            copied += copy_file(&entry_path, &dest_path, options)?;
+            reporter.advance(entry.metadata().map(|m| m.len()).unwrap_or_default());
+        }
+    }
+
+    // After its contents, writing them would update the folder's modification time
+    if options.preserve_metadata {
+        preserve_metadata(src, dst);
+    }
+
+    Ok(copied)
+}
+
+/// Total size of all files below a folder
+pub fn dir_size(folder: &Path) -> u64 {
+    WalkDir::new(folder)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// App data folder used by the desktop app, so the CLI shares its
+/// history and presets
+pub fn app_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_IDENTIFIER))
+}
+
+pub fn is_backup_folder(path: &Path) -> bool {
+    path.parent()
+        .and_then(|parent| parent.file_name())
+        .map(|name| name == "Backup")
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(root: &Path) -> ProjectFolder {
+        ProjectFolder {
+            root: root.to_path_buf(),
+            sets: vec![root.join("Song.als")],
+        }
+    }
+
+    fn plan(folders: &[&str], target: &Path, policy: ConflictPolicy, mirror: bool) -> Result<Vec<PlannedMove>> {
+        let folders = folders.iter().map(|f| folder(Path::new(f))).collect();
+        plan_moves(folders, target.to_str().unwrap(), &TargetTemplate::default(), policy, mirror)
+    }
+
+    #[test]
+    fn plans_folders_without_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path();
+        let planned = plan(&["/src/Demo", "/src/Track1"], target, ConflictPolicy::Fail, false).unwrap();
+
+        assert_eq!(planned[0].target, target.join("Demo"));
+        assert_eq!(planned[1].target, target.join("Track1"));
+        assert!(planned.iter().all(|p| p.conflict.is_none()));
+    }
+
+    #[test]
+    fn fails_on_folders_in_the_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path();
+        fs::create_dir(target.join("Demo")).unwrap();
+
+        let error = plan(&["/src/Demo"], target, ConflictPolicy::Fail, false).unwrap_err().to_string();
+        assert!(error.contains("already exist in the target"), "{}", error);
+        assert!(!error.contains("same path"), "{}", error);
+    }
+
+    #[test]
+    fn fails_on_folders_of_the_same_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path();
+
+        let error = plan(&["/a/Demo", "/b/Demo"], target, ConflictPolicy::Fail, false)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("same path"), "{}", error);
+        assert!(!error.contains("already exist in the target"), "{}", error);
+    }
+
+    #[test]
+    fn renames_past_every_taken_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path();
+        fs::create_dir(target.join("Demo")).unwrap();
+        fs::create_dir(target.join("Demo (2)")).unwrap();
+
+        let planned = plan(&["/a/Demo", "/b/Demo"], target, ConflictPolicy::Rename, false).unwrap();
+
+        assert_eq!(planned[0].target, target.join("Demo (3)"));
+        assert_eq!(planned[1].target, target.join("Demo (4)"));
+        let existing = &planned[0].conflict.as_ref().unwrap().existing;
+        assert_eq!(existing, &target.join("Demo"));
+    }
+
+    #[test]
+    fn keeps_the_target_for_other_policies() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path();
+        fs::create_dir(target.join("Demo")).unwrap();
+
+        for policy in [ConflictPolicy::Skip, ConflictPolicy::Merge, ConflictPolicy::Overwrite] {
+            let planned = plan(&["/src/Demo"], target, policy, false).unwrap();
+            assert_eq!(planned[0].target, target.join("Demo"));
+            assert_eq!(planned[0].conflict.as_ref().unwrap().policy, policy);
+        }
+    }
+
+    #[test]
+    fn mirror_only_conflicts_within_the_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path();
+        fs::create_dir(target.join("Demo")).unwrap();
+
+        let planned = plan(&["/src/Demo"], target, ConflictPolicy::Fail, true).unwrap();
+        assert!(planned[0].conflict.is_none());
+
+        assert!(plan(&["/a/Demo", "/b/Demo"], target, ConflictPolicy::Fail, true).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+mod constants;
+mod exclusions;
+mod file_utils;
+mod xml_utils;
+mod models;
+mod events;
+mod pool;
+mod reporter;
+mod set_info;
+mod target_template;
+
+pub use constants::*;
+pub use exclusions::*;
+pub use file_utils::*;
+pub use xml_utils::*;
+pub use models::*;
+pub use events::*;
+pub use pool::*;
+pub use reporter::*;
+pub use set_info::*;
+pub use target_template::*;
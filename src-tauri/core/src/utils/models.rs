@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Progress {
+    pub job_id: String,
+    pub progress: usize,
+    pub file_name: String,
+    pub is_error: bool,
+    pub is_skipped: bool,
+    pub error_msg: String,
+}
+
+/// Event emitted on the `job-progress` channel while a job runs
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobEvent {
+    pub job_id: String,
+    #[serde(flatten)]
+    pub kind: JobEventKind,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobEventKind {
+    /// A project file was processed, skipped or failed
+    Project(Progress),
+    /// A job, or one of its project files, entered a new phase
+    Phase {
+        phase: Phase,
+        file_name: Option<String>,
+    },
+    /// A sample referenced by a project file was handled
+    Sample {
+        file_name: String,
+        sample: String,
+        status: SampleStatus,
+    },
+    /// Bytes written so far, with throughput in bytes per second
+    Bytes {
+        bytes_done: u64,
+        bytes_total: u64,
+        throughput: u64,
+        eta_secs: Option<u64>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Scanning,
+    Moving,
+    Parsing,
+    Copying,
+    Compressing,
+    // Checking a copied project folder before its source is removed
+    Verifying,
+    // Unpacking a project archive
+    Extracting,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleStatus {
+    Copied,
+    Skipped,
+    Missing,
+    // Copied, but the copy did not match the sample after every retry
+    Mismatched,
+}
+
+/// A project file at its new location after its folder was moved or copied
+#[derive(Clone, Debug)]
+pub struct MovedFile {
+    // Where the project file was before it was moved
+    pub source: PathBuf,
+    pub file_path: PathBuf,
+    // Zero when the folder was renamed instead of copied, and for all
+    // but the first project file of a folder
+    pub bytes_copied: u64,
+    pub conflict: Option<Conflict>,
+}
+
+/// A project folder that could not be moved or copied. When moving, its
+/// source is only removed after a verified copy, so its sets are still
+/// where they were.
+#[derive(Clone, Debug)]
+pub struct FailedMove {
+    pub folder: ProjectFolder,
+    pub error: String,
+    pub conflict: Option<Conflict>,
+}
+
+/// What happened to the planned project folders of a mover job
+#[derive(Clone, Debug, Default)]
+pub struct Moves {
+    pub moved: Vec<MovedFile>,
+    pub failed: Vec<FailedMove>,
+}
+
+/// What the mover does when a project folder already exists in the target
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Leave the project where it is
+    Skip,
+    /// Move it next to the existing folder as `Name (2)`
+    Rename,
+    /// Add its files to the existing folder, keeping the newer of two files
+    Merge,
+    /// Add its files to the existing folder, replacing files with the same name
+    Overwrite,
+    /// Stop the job before anything is moved
+    #[default]
+    Fail,
+}
+
+impl ConflictPolicy {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "skipped",
+            ConflictPolicy::Rename => "renamed",
+            ConflictPolicy::Merge => "merged",
+            ConflictPolicy::Overwrite => "overwritten",
+            ConflictPolicy::Fail => "failed",
+        }
+    }
+}
+
+/// A project folder that already existed in the target
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Conflict {
+    pub existing: PathBuf,
+    pub policy: ConflictPolicy,
+}
+
+/// Where a project folder is moved to, decided before anything is moved
+#[derive(Clone, Debug)]
+pub struct PlannedMove {
+    pub folder: ProjectFolder,
+    pub target: PathBuf,
+    pub conflict: Option<Conflict>,
+}
+
+/// A project folder and the project files inside it, moved as one
+#[derive(Clone, Debug)]
+pub struct ProjectFolder {
+    pub root: PathBuf,
+    pub sets: Vec<PathBuf>,
+}
+
+/// How `find_by_extension` walks a folder
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiscoveryOptions {
+    // Levels of folders to walk, 1 only finds files directly in the folder, none for unlimited
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    // Walk into symlinked folders, symlink loops are reported and skipped
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    // Skip hidden folders and system folders like `$RECYCLE.BIN`
+    #[serde(default = "enabled")]
+    pub skip_hidden: bool,
+    // Skip the `Ableton Project Info` folder of every project
+    #[serde(default = "enabled")]
+    pub skip_project_info: bool,
+    // Folders never walked into, e.g. the mover target inside the scanned folder
+    #[serde(default)]
+    pub skip_folders: Vec<PathBuf>,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        DiscoveryOptions {
+            max_depth: None,
+            follow_symlinks: false,
+            skip_hidden: true,
+            skip_project_info: true,
+            skip_folders: Vec::new(),
+        }
+    }
+}
+
+/// How files are copied by copify and the mover
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CopyOptions {
+    // Keep timestamps, permissions and extended attributes like Finder tags
+    #[serde(default = "enabled")]
+    pub preserve_metadata: bool,
+    // Compare the checksum of every copy with its source and copy again when they differ
+    #[serde(default)]
+    pub verify: bool,
+    // Skip files that are already up to date at the destination
+    #[serde(default)]
+    pub incremental: Incremental,
+}
+
+/// How a copy decides that a file at the destination is already up to date.
+///
+/// An interrupted copy never looks up to date, so running the same job
+/// again resumes where it stopped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Incremental {
+    /// Always copy
+    #[default]
+    Off,
+    /// Same size and modification time, needs `preserve_metadata`
+    SizeAndTime,
+    /// Same size and content
+    Checksum,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        CopyOptions {
+            preserve_metadata: true,
+            verify: false,
+            incremental: Incremental::Off,
+        }
+    }
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// Files found by `find_by_extension` and the folders it could not read
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Discovery {
+    pub files: Vec<PathBuf>,
+    pub errors: Vec<ScanError>,
+}
+
+/// A folder or file that could not be read while scanning
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScanError {
+    pub path: Option<PathBuf>,
+    pub message: String,
+}
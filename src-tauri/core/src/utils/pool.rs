@@ -0,0 +1,58 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::thread;
+
+/// Number of workers to use when a job does not configure its concurrency
+pub fn default_concurrency() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Run `task` for every item on at most `concurrency` worker threads.
+///
+/// Items with the same key form a group that is handled by a single
+/// worker in the original order, so two items of a group never run at
+/// the same time. A `concurrency` of `0` uses [`default_concurrency`].
+pub fn run_grouped<T, K, F>(items: Vec<T>, key: impl Fn(&T) -> K, concurrency: usize, task: F)
+where
+    T: Send,
+    K: Eq + Hash,
+    F: Fn(T) + Sync,
+{
+    let mut index: HashMap<K, usize> = HashMap::new();
+    let mut groups: Vec<Vec<T>> = Vec::new();
+
+    for item in items {
+        let group = *index.entry(key(&item)).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[group].push(item);
+    }
+
+    let workers = match concurrency {
+        0 => default_concurrency(),
+        n => n,
+    }
+    .min(groups.len());
+
+    let queue = Mutex::new(groups.into_iter().collect::<VecDeque<_>>());
+    let task = &task;
+    let queue = &queue;
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(move || loop {
+                let group = match queue.lock().unwrap().pop_front() {
+                    Some(group) => group,
+                    None => break,
+                };
+                for item in group {
+                    task(item);
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,122 @@
+use crate::utils::{JobEvent, JobEventKind, Phase, Progress, SampleStatus};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two `Bytes` events so copying many small
+/// samples does not flood the frontend
+const BYTES_EVENT_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Receives the progress events of a job, e.g. to forward them to a
+/// window or print them. Any `Fn(JobEvent)` closure is a sink.
+pub trait ProgressSink: Send + Sync {
+    fn send(&self, event: JobEvent);
+}
+
+impl<F: Fn(JobEvent) + Send + Sync> ProgressSink for F {
+    fn send(&self, event: JobEvent) {
+        self(event)
+    }
+}
+
+/// Sink that drops every event, for callers that only need the result
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn send(&self, _event: JobEvent) {}
+}
+
+/// Sends typed progress events for a single job and keeps track of the
+/// bytes written, throughput and ETA. Shared by all workers of the job.
+pub struct Reporter {
+    job_id: String,
+    sink: Box<dyn ProgressSink>,
+    bytes_done: AtomicU64,
+    bytes_total: AtomicU64,
+    started: Instant,
+    last_bytes_event: Mutex<Option<Instant>>,
+}
+
+impl Reporter {
+    pub fn new(job_id: &str, sink: impl ProgressSink + 'static) -> Self {
+        Reporter {
+            job_id: job_id.to_string(),
+            sink: Box::new(sink),
+            bytes_done: AtomicU64::new(0),
+            bytes_total: AtomicU64::new(0),
+            started: Instant::now(),
+            last_bytes_event: Mutex::new(None),
+        }
+    }
+
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+
+    pub fn phase(&self, phase: Phase, file_name: Option<&Path>) {
+        self.send(JobEventKind::Phase {
+            phase,
+            file_name: file_name.map(|f| f.to_string_lossy().to_string()),
+        });
+    }
+
+    pub fn project(&self, progress: Progress) {
+        self.send(JobEventKind::Project(progress));
+    }
+
+    pub fn sample(&self, file_name: &Path, sample: &str, status: SampleStatus) {
+        self.send(JobEventKind::Sample {
+            file_name: file_name.to_string_lossy().to_string(),
+            sample: sample.to_string(),
+            status,
+        });
+    }
+
+    /// Add bytes that are about to be written to the job total
+    pub fn add_total(&self, bytes: u64) {
+        self.bytes_total.fetch_add(bytes, Ordering::SeqCst);
+    }
+
+    /// Record written bytes and emit a throttled `Bytes` event
+    pub fn advance(&self, bytes: u64) {
+        self.bytes_done.fetch_add(bytes, Ordering::SeqCst);
+
+        let now = Instant::now();
+        {
+            let mut last = self.last_bytes_event.lock().unwrap();
+            if last.is_some_and(|at| now.duration_since(at) < BYTES_EVENT_INTERVAL) {
+                return;
+            }
+            *last = Some(now);
+        }
+        self.send_bytes();
+    }
+
+    /// Emit the final byte counts regardless of throttling
+    pub fn flush(&self) {
+        self.send_bytes();
+    }
+
+    fn send_bytes(&self) {
+        let bytes_done = self.bytes_done.load(Ordering::SeqCst);
+        let bytes_total = self.bytes_total.load(Ordering::SeqCst).max(bytes_done);
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let throughput = if elapsed > 0.0 { (bytes_done as f64 / elapsed) as u64 } else { 0 };
+        let eta_secs = (throughput > 0).then(|| (bytes_total - bytes_done) / throughput);
+
+        self.send(JobEventKind::Bytes {
+            bytes_done,
+            bytes_total,
+            throughput,
+            eta_secs,
+        });
+    }
+
+    fn send(&self, kind: JobEventKind) {
+        self.sink.send(JobEvent {
+            job_id: self.job_id.clone(),
+            kind,
+        });
+    }
+}
@@ -0,0 +1,167 @@
+use crate::prelude::*;
+use crate::utils::decode_xml_value;
+use chrono::{DateTime, Local};
+use flate2::read::GzDecoder;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::QName;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// What a set contains, read without modifying it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SetInfo {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<DateTime<Local>>,
+    // e.g. "Ableton Live 11.3.4"
+    pub live_version: Option<String>,
+    // Song tempo in BPM
+    pub tempo: Option<f64>,
+    // Names of the VST, VST3 and Audio Unit plugins, sorted
+    pub plugins: Vec<String>,
+    pub samples: Vec<SampleInfo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SampleInfo {
+    pub path: String,
+    pub exists: bool,
+}
+
+impl SetInfo {
+    pub fn missing_samples(&self) -> impl Iterator<Item = &SampleInfo> {
+        self.samples.iter().filter(|s| !s.exists)
+    }
+}
+
+/// Read the Live version, tempo, plugins and referenced samples of a set
+pub fn read_set_info(set: &Path) -> Result<SetInfo> {
+    let metadata = fs::metadata(set).on(Operation::Read, set)?;
+    let file = File::open(set).on(Operation::Read, set)?;
+    let mut reader = Reader::from_reader(BufReader::new(GzDecoder::new(BufReader::new(file))));
+    reader.trim_text(true);
+
+    let mut live_version = None;
+    let mut tempo = None;
+    let mut plugins = BTreeSet::new();
+    let mut samples = BTreeSet::new();
+    let mut inside_sample_ref = false;
+    // Names of the open elements, to find the song tempo
+    let mut open: Vec<Vec<u8>> = Vec::new();
+    let mut inside_plugin = false;
+    let mut buf = Vec::new();
+
+    loop {
+        let event = reader
+            .read_event_into(&mut buf)
+            .map_err(|e| Error::Xml(format!("{} at position {}: {}", set.display(), reader.buffer_position(), e)))?;
+
+        match event {
+            Event::Start(ref e) => {
+                open.push(e.name().as_ref().to_vec());
+                match e.name().as_ref() {
+                    b"Ableton" => live_version = attribute(e, b"Creator"),
+                    b"SampleRef" => inside_sample_ref = true,
+                    b"VstPluginInfo" | b"Vst3PluginInfo" | b"AuPluginInfo" => inside_plugin = true,
+                    _ => {}
+                }
+            }
+            Event::Empty(ref e) if inside_sample_ref && e.name().as_ref() == b"Path" => {
+                if let Some(path) = attribute(e, b"Value").filter(|p| !p.is_empty()) {
+                    samples.insert(decode_xml_value(&path));
+                }
+            }
+            // Every track's mixer has a tempo, only the main track's is the song tempo
+            Event::Empty(ref e) if e.name().as_ref() == b"Manual" && is_song_tempo(&open) => {
+                tempo = attribute(e, b"Value").and_then(|value| value.parse().ok());
+            }
+            // VST names the plugin `PlugName`, VST3 and Audio Units `Name`
+            Event::Empty(ref e) if inside_plugin && matches!(e.name().as_ref(), b"PlugName" | b"Name") => {
+                if let Some(name) = attribute(e, b"Value").filter(|n| !n.is_empty()) {
+                    plugins.insert(decode_xml_value(&name));
+                }
+            }
+            Event::End(ref e) => {
+                open.pop();
+                match e.name().as_ref() {
+                    b"SampleRef" => inside_sample_ref = false,
+                    b"VstPluginInfo" | b"Vst3PluginInfo" | b"AuPluginInfo" => inside_plugin = false,
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(SetInfo {
+        path: set.to_path_buf(),
+        size: metadata.len(),
+        modified: metadata.modified().ok().map(DateTime::<Local>::from),
+        live_version,
+        tempo,
+        plugins: plugins.into_iter().collect(),
+        samples: samples
+            .into_iter()
+            .map(|path| SampleInfo {
+                exists: Path::new(&path).is_file(),
+                path,
+            })
+            .collect(),
+    })
+}
+
+/// `MasterTrack/DeviceChain/Mixer/Tempo`, Live 12 calls it `MainTrack`
+fn is_song_tempo(open: &[Vec<u8>]) -> bool {
+    match open {
+        [.., track, chain, mixer, tempo] => {
+            matches!(track.as_slice(), b"MasterTrack" | b"MainTrack")
+                && chain == b"DeviceChain"
+                && mixer == b"Mixer"
+                && tempo == b"Tempo"
+        }
+        _ => false,
+    }
+}
+
+fn attribute(e: &BytesStart, key: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key == QName(key))
+        .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn reads_the_tempo_of_the_main_track() {
+        for main in ["MasterTrack", "MainTrack"] {
+            let dir = tempfile::tempdir().unwrap();
+            let set = dir.path().join("Song.als");
+            // Tracks come first, each with a dormant tempo in its mixer
+            let xml = format!(
+                r#"<Ableton Creator="Ableton Live 12.0"><LiveSet>
+                <Tracks><AudioTrack><DeviceChain><Mixer><Tempo><Manual Value="120"/></Tempo></Mixer></DeviceChain></AudioTrack></Tracks>
+                <{main}><DeviceChain><Mixer><Tempo><Manual Value="87.5"/></Tempo></Mixer></DeviceChain></{main}>
+                </LiveSet></Ableton>"#
+            );
+            let mut encoder = GzEncoder::new(File::create(&set).unwrap(), Compression::default());
+            encoder.write_all(xml.as_bytes()).unwrap();
+            encoder.finish().unwrap();
+
+            let info = read_set_info(&set).unwrap();
+            assert_eq!(info.tempo, Some(87.5), "{}", main);
+            assert_eq!(info.live_version.as_deref(), Some("Ableton Live 12.0"));
+        }
+    }
+}
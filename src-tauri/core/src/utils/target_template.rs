@@ -0,0 +1,218 @@
+use crate::prelude::*;
+use crate::utils::{read_set_info, ProjectFolder};
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Where the mover puts a project folder inside the target, built from
+/// the project's metadata, e.g. `{year}/{month}/{project}` or
+/// `{tempo}bpm/{project}`.
+///
+/// Dates are the modification time of the newest set in the folder,
+/// tempo and Live version are read from that set.
+#[derive(Clone, Debug)]
+pub struct TargetTemplate {
+    parts: Vec<Part>,
+    // Scanned folder whose subfolders are kept above each project
+    hierarchy_of: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug)]
+enum Part {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Placeholder {
+    // Name of the project folder
+    Project,
+    // Name of the newest set, without extension
+    Set,
+    Year,
+    Month,
+    Day,
+    // Rounded to whole BPM
+    Tempo,
+    // e.g. `11.3.4`
+    Live,
+}
+
+impl Placeholder {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "project" => Some(Placeholder::Project),
+            "set" => Some(Placeholder::Set),
+            "year" => Some(Placeholder::Year),
+            "month" => Some(Placeholder::Month),
+            "day" => Some(Placeholder::Day),
+            "tempo" => Some(Placeholder::Tempo),
+            "live" => Some(Placeholder::Live),
+            _ => None,
+        }
+    }
+}
+
+/// Value used when a project has no value for a placeholder
+static UNKNOWN: &str = "Unknown";
+
+impl Default for TargetTemplate {
+    fn default() -> Self {
+        TargetTemplate {
+            parts: vec![Part::Placeholder(Placeholder::Project)],
+            hierarchy_of: None,
+        }
+    }
+}
+
+impl TargetTemplate {
+    /// Parse a template, failing on unknown placeholders and on paths
+    /// that would leave the target
+    pub fn parse(template: &str) -> Result<Self> {
+        let invalid = |reason: &str| Error::MoverFailed(format!("Invalid target template {}: {}", template, reason));
+
+        let relative = Path::new(template).components().all(|c| matches!(c, Component::Normal(_)));
+        if template.trim().is_empty() || !relative {
+            return Err(invalid("it must be a relative path inside the target"));
+        }
+
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Text(rest[..start].to_string()));
+            }
+            let end = rest[start..].find('}').ok_or_else(|| invalid("unclosed {"))? + start;
+            let name = &rest[start + 1..end];
+            let placeholder = Placeholder::from_name(name).ok_or_else(|| invalid(&format!("unknown placeholder {{{}}}", name)))?;
+            parts.push(Part::Placeholder(placeholder));
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Text(rest.to_string()));
+        }
+
+        Ok(TargetTemplate { parts, hierarchy_of: None })
+    }
+
+    /// Keep the folders between `folder` and each project above the
+    /// rendered path, so `Clients/Acme/Track1` stays apart from
+    /// `Clients/Other/Track1`
+    pub fn preserving_hierarchy_of(mut self, folder: &Path) -> Self {
+        self.hierarchy_of = Some(folder.to_path_buf());
+        self
+    }
+
+    /// The path of a project folder relative to the target
+    pub fn render(&self, folder: &ProjectFolder) -> PathBuf {
+        let newest = folder
+            .sets
+            .iter()
+            .max_by_key(|set| fs::metadata(set).and_then(|m| m.modified()).ok());
+        let needs_info = self
+            .parts
+            .iter()
+            .any(|part| matches!(part, Part::Placeholder(Placeholder::Tempo | Placeholder::Live)));
+        let info = newest.filter(|_| needs_info).and_then(|set| read_set_info(set).ok());
+        let modified = newest
+            .and_then(|set| fs::metadata(set).and_then(|m| m.modified()).ok())
+            .map(DateTime::<Local>::from);
+
+        let rendered: String = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.clone(),
+                Part::Placeholder(placeholder) => {
+                    let value = match placeholder {
+                        Placeholder::Project => folder.root.file_name().map(|n| n.to_string_lossy().into_owned()),
+                        Placeholder::Set => newest
+                            .and_then(|set| set.file_stem())
+                            .map(|stem| stem.to_string_lossy().into_owned()),
+                        Placeholder::Year => modified.map(|m| m.format("%Y").to_string()),
+                        Placeholder::Month => modified.map(|m| m.format("%m").to_string()),
+                        Placeholder::Day => modified.map(|m| m.format("%d").to_string()),
+                        Placeholder::Tempo => info.as_ref().and_then(|i| i.tempo).map(|t| t.round().to_string()),
+                        Placeholder::Live => info
+                            .as_ref()
+                            .and_then(|i| i.live_version.as_deref())
+                            .and_then(|v| v.split_whitespace().last())
+                            .map(str::to_string),
+                    };
+                    sanitize(value.as_deref().unwrap_or(UNKNOWN))
+                }
+            })
+            .collect();
+
+        let hierarchy = self
+            .hierarchy_of
+            .as_ref()
+            .and_then(|scanned| folder.root.parent()?.strip_prefix(scanned).ok())
+            .unwrap_or(Path::new(""));
+
+        hierarchy.join(rendered)
+    }
+}
+
+/// A value never adds folders or characters Windows does not allow
+fn sanitize(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') || c.is_control() { '_' } else { c })
+        .collect();
+
+    match cleaned.trim() {
+        "" | "." | ".." => UNKNOWN.to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(root: &str) -> ProjectFolder {
+        ProjectFolder {
+            root: PathBuf::from(root),
+            sets: vec![Path::new(root).join("Song v2.als")],
+        }
+    }
+
+    fn render(template: &str, root: &str) -> PathBuf {
+        TargetTemplate::parse(template).unwrap().render(&folder(root))
+    }
+
+    #[test]
+    fn renders_text_and_placeholders() {
+        assert_eq!(render("{project}", "/src/Track1"), PathBuf::from("Track1"));
+        assert_eq!(render("Archive/{project} - {set}", "/src/Track1"), PathBuf::from("Archive/Track1 - Song v2"));
+    }
+
+    #[test]
+    fn uses_unknown_for_missing_values() {
+        // The set does not exist, so it has neither a date nor a tempo
+        assert_eq!(render("{year}/{tempo}bpm/{project}", "/src/Track1"), PathBuf::from("Unknown/Unknownbpm/Track1"));
+    }
+
+    #[test]
+    fn never_adds_folders_from_values() {
+        assert_eq!(render("{project}", "/src/AC:DC * Live?"), PathBuf::from("AC_DC _ Live_"));
+        assert_eq!(render("{project}", "/src/.."), PathBuf::from("Unknown"));
+    }
+
+    #[test]
+    fn keeps_the_hierarchy_below_the_scanned_folder() {
+        let template = TargetTemplate::default().preserving_hierarchy_of(Path::new("/src"));
+
+        assert_eq!(template.render(&folder("/src/Clients/Acme/Track1")), PathBuf::from("Clients/Acme/Track1"));
+        assert_eq!(template.render(&folder("/src/Track1")), PathBuf::from("Track1"));
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        for template in ["", " ", "/abs/{project}", "../{project}", "a/../{project}", "{nope}", "{project"] {
+            assert!(TargetTemplate::parse(template).is_err(), "{}", template);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod models;
+pub(crate) mod watch;
+
+pub use models::*;
+pub use watch::*;
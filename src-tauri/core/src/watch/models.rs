@@ -0,0 +1,19 @@
+use crate::copify::CopifySettings;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WatchSettings {
+    // Project folders to watch, including their subfolders
+    pub folders: Vec<String>,
+    // Copify settings for saved sets, `folder` is replaced by the watched folder
+    pub settings: CopifySettings,
+    // Time without changes before a saved set is collected
+    #[serde(default = "default_settle_ms")]
+    pub settle_ms: u64,
+}
+
+/// Live writes a set in several steps, wait until it has been quiet this long
+pub fn default_settle_ms() -> u64 {
+    2000
+}
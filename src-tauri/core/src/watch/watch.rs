@@ -0,0 +1,185 @@
+use crate::error::Error;
+use crate::jobs::CancelToken;
+use crate::utils::{is_backup_folder, ALS};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Size and modification time of a set, used to tell whether it is
+/// still being written
+type Snapshot = (u64, Option<SystemTime>);
+
+/// Sets rewritten by `on_saved`, so their own change is not collected again
+type Written = Arc<Mutex<HashMap<PathBuf, Snapshot>>>;
+
+/// Watches project folders and hands every saved set to a callback
+/// once Live has finished writing it.
+///
+/// Rapid saves of the same set are debounced into one run, sets in a
+/// `Backup` folder are ignored and sets are handled one at a time in
+/// the order they settled. Watching stops when the watcher is dropped.
+pub struct ProjectWatcher {
+    folders: Vec<PathBuf>,
+    stop: CancelToken,
+    _watcher: RecommendedWatcher,
+}
+
+impl ProjectWatcher {
+    pub fn start(
+        folders: &[PathBuf],
+        settle: Duration,
+        on_saved: impl FnMut(&Path) + Send + 'static,
+    ) -> Result<Self, Error> {
+        let (events_tx, events_rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            if let Ok(event) = result {
+                // Renames are modify events, which covers saving through a temp file
+                if event.kind.is_create() || event.kind.is_modify() {
+                    for path in event.paths {
+                        let _ = events_tx.send(path);
+                    }
+                }
+            }
+        })
+        .map_err(|e| Error::Generic(format!("Could not start watching: {}", e)))?;
+
+        for folder in folders {
+            watcher
+                .watch(folder, RecursiveMode::Recursive)
+                .map_err(|e| Error::Generic(format!("Could not watch {}: {}", folder.display(), e)))?;
+        }
+
+        let stop = CancelToken::default();
+        let written = Written::default();
+        let (queue_tx, queue_rx) = mpsc::channel();
+
+        {
+            let stop = stop.clone();
+            let written = written.clone();
+            thread::spawn(move || debounce(events_rx, queue_tx, settle, stop, written));
+        }
+        {
+            let stop = stop.clone();
+            thread::spawn(move || work(queue_rx, on_saved, stop, written));
+        }
+
+        Ok(ProjectWatcher {
+            folders: folders.to_vec(),
+            stop,
+            _watcher: watcher,
+        })
+    }
+
+    pub fn folders(&self) -> &[PathBuf] {
+        &self.folders
+    }
+}
+
+impl Drop for ProjectWatcher {
+    fn drop(&mut self) {
+        // A set that is being collected finishes, nothing queued after it runs
+        self.stop.cancel();
+    }
+}
+
+/// Collect change events and queue a set once it had no changes for
+/// `settle` and its size and modification time stopped changing
+fn debounce(
+    events: Receiver<PathBuf>,
+    queue: Sender<(PathBuf, Snapshot)>,
+    settle: Duration,
+    stop: CancelToken,
+    written: Written,
+) {
+    let mut pending: HashMap<PathBuf, (Instant, Snapshot)> = HashMap::new();
+    let tick = (settle / 4).max(Duration::from_millis(50));
+
+    while !stop.is_cancelled() {
+        match events.recv_timeout(tick) {
+            Ok(path) => {
+                if is_watched_set(&path) {
+                    if let Some(current) = snapshot(&path) {
+                        pending.insert(path, (Instant::now(), current));
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let now = Instant::now();
+        let settled: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, (changed_at, _))| now.duration_since(*changed_at) >= settle)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in settled {
+            let (_, before) = pending.remove(&path).unwrap();
+
+            match snapshot(&path) {
+                // Still being written, wait for another quiet period
+                Some(current) if current != before => {
+                    pending.insert(path, (now, current));
+                }
+                // Our own rewrite of the set needs no second run
+                Some(current) if written.lock().unwrap().get(&path) == Some(&current) => {}
+                Some(current) => {
+                    let _ = queue.send((path, current));
+                }
+                // Removed or renamed away before it settled
+                None => {}
+            }
+        }
+    }
+}
+
+/// Hand queued sets to `on_saved` one at a time
+fn work(
+    queue: Receiver<(PathBuf, Snapshot)>,
+    mut on_saved: impl FnMut(&Path),
+    stop: CancelToken,
+    written: Written,
+) {
+    for (path, queued) in queue {
+        if stop.is_cancelled() {
+            break;
+        }
+
+        // Saved again while queued, it is queued again once that save settled
+        if snapshot(&path) != Some(queued) {
+            continue;
+        }
+
+        on_saved(&path);
+
+        if let Some(current) = snapshot(&path) {
+            written.lock().unwrap().insert(path, current);
+        }
+    }
+}
+
+/// The watched folder a set was saved in, so exclude patterns are
+/// relative to the same folder as when it is scanned
+pub fn watched_folder<'a>(folders: &'a [PathBuf], set: &'a Path) -> &'a Path {
+    folders
+        .iter()
+        .filter(|folder| set.starts_with(folder))
+        .max_by_key(|folder| folder.components().count())
+        .map(PathBuf::as_path)
+        .unwrap_or_else(|| set.parent().unwrap_or(set))
+}
+
+fn is_watched_set(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == ALS) && !is_backup_folder(path)
+}
+
+fn snapshot(path: &Path) -> Option<Snapshot> {
+    let metadata = fs::metadata(path).ok().filter(|m| m.is_file())?;
+    Some((metadata.len(), metadata.modified().ok()))
+}
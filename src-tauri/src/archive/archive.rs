@@ -0,0 +1,40 @@
+use crate::copify::collect_project;
+use crate::jobs::WindowSink;
+use livesaver_core::archive::{ArchiveFormat, ImportedArchive, Manifest};
+use livesaver_core::copify::CopifySettings;
+use livesaver_core::error::Error;
+use livesaver_core::history::History;
+use livesaver_core::jobs::Jobs;
+use livesaver_core::utils::Reporter;
+use std::path::Path;
+
+#[tauri::command]
+pub async fn export_archive(
+    window: tauri::Window,
+    jobs: tauri::State<'_, Jobs>,
+    history: tauri::State<'_, History>,
+    project: String,
+    output: String,
+    format: ArchiveFormat,
+    collect: Option<CopifySettings>,
+) -> Result<Manifest, Error> {
+    collect_project(&window, &jobs, &history, &project, collect, "archive-progress")?;
+
+    let job = jobs.start();
+    let reporter = Reporter::new(&job.id, WindowSink::new(&window, "archive-progress"));
+
+    livesaver_core::archive::export_archive(Path::new(&project), Path::new(&output), format, &job.token, &reporter)
+}
+
+#[tauri::command]
+pub async fn import_archive(
+    window: tauri::Window,
+    jobs: tauri::State<'_, Jobs>,
+    archive: String,
+    destination: String,
+) -> Result<ImportedArchive, Error> {
+    let job = jobs.start();
+    let reporter = Reporter::new(&job.id, WindowSink::new(&window, "archive-progress"));
+
+    livesaver_core::archive::import_archive(Path::new(&archive), Path::new(&destination), &reporter)
+}
@@ -0,0 +1,3 @@
+pub(crate) mod archive;
+
+pub use archive::*;
@@ -0,0 +1,17 @@
+use livesaver_core::copify::{find_backups, restore, Backup, BackupLocation};
+use livesaver_core::error::Error;
+use std::path::Path;
+
+#[tauri::command]
+pub async fn list_backups(set: String, location: Option<BackupLocation>) -> Result<Vec<Backup>, Error> {
+    find_backups(Path::new(&set), &location.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn restore_backup(
+    set: String,
+    backup: String,
+    location: Option<BackupLocation>,
+) -> Result<(), Error> {
+    restore(Path::new(&set), Path::new(&backup), &location.unwrap_or_default())
+}
@@ -1,99 +1,82 @@
-use crate::copify::models::CopifySettings;
-use crate::copify::update_sample_refs;
-use crate::utils::*;
-use crate::error::Error;
-use std::fs;
-use std::path::Path;
+use crate::jobs::WindowSink;
+use chrono::Local;
+use livesaver_core::copify::{copify_job, CopifySettings};
+use livesaver_core::error::Error;
+use livesaver_core::history::{record_job, History, JobSettings};
+use livesaver_core::jobs::{JobKind, JobStarted, Jobs};
+use livesaver_core::report::{ProjectStatus, RunReport};
+use livesaver_core::utils::{find_by_extension, DiscoveryOptions, Reporter, ALS};
 use std::path::PathBuf;
 use tauri::Emitter;
 
 #[tauri::command]
-pub async fn copify(window: tauri::Window, settings: CopifySettings) -> Result<(), Error> {
-    let files = find_by_extension(settings.folder.as_str(), ALS);
-
-    if files.is_empty() {
-        return Err(Error::FileNotFound("No Ableton Live project files found".to_string()));
-    }
-
-    let progress_name = "copify-progress";
-
-    for (i, file_path) in files.iter().enumerate() {
-        let progress_value = ((i + 1) * 100) / files.len();
-        let file_name_str = file_path.to_string_lossy().to_string();
-
-        if should_run(file_path, settings.exclude_files.to_vec()) {
-            let progress = match run_copify(file_path, &settings) {
-                Ok(_) => on_success(file_name_str.clone(), progress_value),
-                Err(e) => on_error(file_name_str.clone(), progress_value, e.to_string())
-            };
-            window.emit(progress_name, progress).unwrap()
-        } else {
-            window.emit(progress_name, on_skip(file_name_str.clone(), progress_value)).unwrap()
-        };
-    }
-    Ok(())
-}
-
-#[tauri::command]
-pub async fn get_als_files(window: tauri::Window, folder: String) -> Result<Vec<PathBuf>, Error> {
-    let files = find_by_extension(folder.as_str(), ALS);
-
-    if files.is_empty() {
-        return Err(Error::FileNotFound("No Ableton Live project files found".to_string()));
-    }
-
-    Ok(files)
+pub async fn copify(
+    window: tauri::Window,
+    jobs: tauri::State<'_, Jobs>,
+    history: tauri::State<'_, History>,
+    settings: CopifySettings,
+) -> Result<RunReport, Error> {
+    let job = jobs.start();
+    let started_at = Local::now();
+    window.emit("job-started", JobStarted { job_id: job.id.clone(), kind: JobKind::Copify }).unwrap();
+
+    let reporter = Reporter::new(&job.id, WindowSink::new(&window, "copify-progress"));
+    let result = copify_job(&job.id, &job.token, &settings, &reporter);
+    record_job(&history, &job.id, JobSettings::Copify(settings), started_at, &result);
+
+    result
 }
 
-/// Find a project files samples and move its samples into its folder.
-///
-/// # Arguments
+/// Collect the samples of a single project with the user's copify
+/// `settings` before it is archived or shared, see
+/// `CopifySettings::for_project`. Does nothing without settings, so
+/// packaging only changes the project when asked to.
 ///
-/// * `file_path` - Ableton project file
-/// * `settings` - Copify process settings
-pub fn run_copify(file_path: &PathBuf, settings: &CopifySettings) -> Result<(), Error> {
-    // Skip project files that are in the Ableton Backup folder
-    if is_backup_folder(file_path) {
+/// The run is recorded in the history and can be cancelled like any
+/// copify run.
+pub(crate) fn collect_project(
+    window: &tauri::Window,
+    jobs: &Jobs,
+    history: &History,
+    project: &str,
+    settings: Option<CopifySettings>,
+    channel: &'static str,
+) -> Result<(), Error> {
+    let Some(settings) = settings else {
         return Ok(());
-    }
+    };
+    let settings = settings.for_project(project);
 
-    let mut xml = file_path.clone();
-    xml.set_extension(XML);
+    let job = jobs.start();
+    let started_at = Local::now();
+    window.emit("job-started", JobStarted { job_id: job.id.clone(), kind: JobKind::Copify }).unwrap();
 
-    if settings.create_backup {
-        create_backup(file_path)?;
-    }
+    let reporter = Reporter::new(&job.id, WindowSink::new(window, channel));
+    let result = copify_job(&job.id, &job.token, &settings, &reporter);
+    record_job(history, &job.id, JobSettings::Copify(settings), started_at, &result);
 
-    decompress(file_path, xml.as_path())?;
-    update_sample_refs(xml.as_path(), settings)?;
-    compress(xml.as_path(), file_path)?;
-    fs::remove_file(xml)?;
+    let report = result?;
+    if report.cancelled {
+        return Err(Error::Cancelled);
+    }
+    if report.count(ProjectStatus::Failed) > 0 {
+        return Err(Error::CopifyFailed(format!("Could not collect the samples of {}", project)));
+    }
 
     Ok(())
 }
 
-fn create_backup(input: &Path) -> Result<(), Error> {
-    if input.to_string_lossy().contains(ALS_BACKUP_EXTENSION) {
-        return Err(Error::CopifyFailed("Input file is not valid to backup".to_string()));
+#[tauri::command]
+pub async fn get_als_files(
+    window: tauri::Window,
+    folder: String,
+    discovery: Option<DiscoveryOptions>,
+) -> Result<Vec<PathBuf>, Error> {
+    let discovery = find_by_extension(folder.as_str(), ALS, &discovery.unwrap_or_default());
+
+    if discovery.files.is_empty() {
+        return Err(Error::FileNotFound("No Ableton Live project files found".to_string()));
     }
 
-    let dir = input
-        .parent()
-        .ok_or_else(|| Error::FileNotFound("Invalid file path".to_string()))?;
-    let filename = input
-        .file_name()
-        .ok_or_else(|| Error::FileNotFound("Invalid file path".to_string()))?;
-
-    let backup_filename = format!(
-        "{}{}",
-        filename.to_string_lossy().replace(ALS_EXTENSION, ""),
-        ALS_BACKUP_EXTENSION
-    );
-
-    let backup_file_ref = dir.join(backup_filename);
-
-    fs::copy(input, &backup_file_ref)?;
-
-    Ok(())
+    Ok(discovery.files)
 }
-
@@ -1,8 +1,5 @@
+pub(crate) mod backup;
 pub(crate) mod copify;
-mod models;
-mod xml;
-
-use xml::*;
 
+pub use backup::*;
 pub use copify::*;
-pub use models::*;
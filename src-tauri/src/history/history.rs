@@ -0,0 +1,39 @@
+use crate::copify::copify;
+use crate::mover::mover;
+use livesaver_core::error::Error;
+use livesaver_core::history::{History, JobEntry, JobRecord, JobSettings};
+use livesaver_core::jobs::Jobs;
+use livesaver_core::report::RunReport;
+use std::path::Path;
+
+/// List past jobs, newest first. With `project` only jobs that scanned
+/// or processed that folder or project file are returned.
+#[tauri::command]
+pub async fn list_jobs(history: tauri::State<'_, History>, project: Option<String>) -> Result<Vec<JobEntry>, Error> {
+    let jobs = history.list()?;
+
+    Ok(jobs
+        .iter()
+        .filter(|job| project.as_ref().is_none_or(|p| job.touches(Path::new(p))))
+        .map(JobRecord::entry)
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_job(history: tauri::State<'_, History>, job_id: String) -> Result<JobRecord, Error> {
+    history.get(&job_id)
+}
+
+/// Run a past job again with the settings it was started with
+#[tauri::command]
+pub async fn rerun_job(
+    window: tauri::Window,
+    jobs: tauri::State<'_, Jobs>,
+    history: tauri::State<'_, History>,
+    job_id: String,
+) -> Result<RunReport, Error> {
+    match history.get(&job_id)?.settings {
+        JobSettings::Copify(settings) => copify(window, jobs, history, settings).await,
+        JobSettings::Mover(settings) => mover(window, jobs, history, settings).await,
+    }
+}
@@ -0,0 +1,3 @@
+pub(crate) mod history;
+
+pub use history::*;
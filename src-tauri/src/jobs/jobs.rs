@@ -0,0 +1,39 @@
+use livesaver_core::error::Error;
+use livesaver_core::jobs::Jobs;
+use livesaver_core::utils::{JobEvent, JobEventKind, ProgressSink};
+use tauri::Emitter;
+
+/// Sends a job's typed progress events to the window. Per project
+/// progress also goes to `progress_channel` in the format the copify
+/// and mover screens listen for.
+pub struct WindowSink {
+    window: tauri::Window,
+    progress_channel: &'static str,
+}
+
+impl WindowSink {
+    pub fn new(window: &tauri::Window, progress_channel: &'static str) -> Self {
+        WindowSink {
+            window: window.clone(),
+            progress_channel,
+        }
+    }
+}
+
+impl ProgressSink for WindowSink {
+    fn send(&self, event: JobEvent) {
+        let _ = match event.kind {
+            JobEventKind::Project(progress) => self.window.emit(self.progress_channel, progress),
+            _ => self.window.emit("job-progress", event),
+        };
+    }
+}
+
+#[tauri::command]
+pub async fn cancel_job(jobs: tauri::State<'_, Jobs>, job_id: String) -> Result<(), Error> {
+    if jobs.cancel(&job_id) {
+        Ok(())
+    } else {
+        Err(Error::Generic(format!("No running job with id {}", job_id)))
+    }
+}
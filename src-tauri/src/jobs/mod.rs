@@ -0,0 +1,3 @@
+pub(crate) mod jobs;
+
+pub use jobs::*;
@@ -1,8 +1,17 @@
+// Feature folders hold a file of the same name, e.g. `copify/copify.rs`
+#![allow(clippy::module_inception)]
+
+mod archive;
 mod copify;
-mod error;
+mod history;
+mod jobs;
 mod mover;
-mod prelude;
-mod utils;
+mod presets;
+mod report;
+mod share;
+mod watch;
+
+use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -11,10 +20,37 @@ pub fn run() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
+        .manage(livesaver_core::jobs::Jobs::default())
+        .manage(crate::watch::Watcher::default())
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            app.manage(livesaver_core::history::History::open(&data_dir));
+            app.manage(livesaver_core::presets::Presets::open(&data_dir));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             crate::copify::copify,
             crate::mover::mover,
-            crate::copify::get_als_files]
+            crate::copify::get_als_files,
+            crate::copify::list_backups,
+            crate::copify::restore_backup,
+            crate::jobs::cancel_job,
+            crate::report::get_report,
+            crate::report::export_report,
+            crate::history::list_jobs,
+            crate::history::get_job,
+            crate::history::rerun_job,
+            crate::presets::list_presets,
+            crate::presets::get_preset,
+            crate::presets::save_preset,
+            crate::presets::rename_preset,
+            crate::presets::delete_preset,
+            crate::watch::start_watch,
+            crate::watch::stop_watch,
+            crate::watch::get_watch_status,
+            crate::archive::export_archive,
+            crate::archive::import_archive,
+            crate::share::share_project]
         )
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,4 +1,3 @@
-mod models;
 pub(crate) mod mover;
 
 pub use mover::*;
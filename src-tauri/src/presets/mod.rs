@@ -0,0 +1,3 @@
+pub(crate) mod presets;
+
+pub use presets::*;
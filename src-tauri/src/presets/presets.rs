@@ -0,0 +1,33 @@
+use livesaver_core::error::Error;
+use livesaver_core::history::JobSettings;
+use livesaver_core::presets::{Preset, Presets};
+
+#[tauri::command]
+pub async fn list_presets(presets: tauri::State<'_, Presets>) -> Result<Vec<Preset>, Error> {
+    presets.list()
+}
+
+#[tauri::command]
+pub async fn get_preset(presets: tauri::State<'_, Presets>, name: String) -> Result<Preset, Error> {
+    presets.get(&name)
+}
+
+/// Create a preset, or replace the settings of the preset with that name
+#[tauri::command]
+pub async fn save_preset(
+    presets: tauri::State<'_, Presets>,
+    name: String,
+    settings: JobSettings,
+) -> Result<Preset, Error> {
+    presets.save(&name, settings)
+}
+
+#[tauri::command]
+pub async fn rename_preset(presets: tauri::State<'_, Presets>, name: String, new_name: String) -> Result<Preset, Error> {
+    presets.rename(&name, &new_name)
+}
+
+#[tauri::command]
+pub async fn delete_preset(presets: tauri::State<'_, Presets>, name: String) -> Result<(), Error> {
+    presets.delete(&name)
+}
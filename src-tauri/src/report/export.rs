@@ -0,0 +1,21 @@
+use livesaver_core::error::Error;
+use livesaver_core::history::History;
+use livesaver_core::report::{find_report, write_report, ReportFormat, RunReport};
+use std::path::Path;
+
+#[tauri::command]
+pub async fn get_report(history: tauri::State<'_, History>, job_id: String) -> Result<RunReport, Error> {
+    find_report(&history, &job_id)
+}
+
+#[tauri::command]
+pub async fn export_report(
+    history: tauri::State<'_, History>,
+    job_id: String,
+    format: ReportFormat,
+    path: String,
+) -> Result<(), Error> {
+    let report = find_report(&history, &job_id)?;
+
+    write_report(&report, format, Path::new(&path))
+}
@@ -0,0 +1,3 @@
+pub(crate) mod share;
+
+pub use share::*;
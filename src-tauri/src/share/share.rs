@@ -0,0 +1,27 @@
+use crate::copify::collect_project;
+use crate::jobs::WindowSink;
+use livesaver_core::copify::CopifySettings;
+use livesaver_core::error::Error;
+use livesaver_core::history::History;
+use livesaver_core::jobs::Jobs;
+use livesaver_core::share::{SharePackage, ShareSettings};
+use livesaver_core::utils::Reporter;
+use std::path::Path;
+
+#[tauri::command]
+pub async fn share_project(
+    window: tauri::Window,
+    jobs: tauri::State<'_, Jobs>,
+    history: tauri::State<'_, History>,
+    project: String,
+    output: String,
+    settings: ShareSettings,
+    collect: Option<CopifySettings>,
+) -> Result<SharePackage, Error> {
+    collect_project(&window, &jobs, &history, &project, collect, "share-progress")?;
+
+    let job = jobs.start();
+    let reporter = Reporter::new(&job.id, WindowSink::new(&window, "share-progress"));
+
+    livesaver_core::share::share_project(Path::new(&project), Path::new(&output), &settings, &job.token, &reporter)
+}
@@ -1,6 +0,0 @@
-pub static SAMPLES_IMPORTED: &str = "Samples/Imported/";
-pub static ALS: &str = "als";
-pub static ALS_EXTENSION: &str = concat!(".", "als");
-pub static ALS_BACKUP_EXTENSION: &str = concat!(".", "als", ".bak");
-pub static XML: &str = "xml";
-pub static XML_EXTENSION: &str = concat!(".", "xml");
@@ -1,11 +0,0 @@
-mod constants;
-mod file_utils;
-mod xml_utils;
-mod models;
-mod events;
-
-pub use constants::*;
-pub use file_utils::*;
-pub use xml_utils::*;
-pub use models::*;
-pub use events::*;
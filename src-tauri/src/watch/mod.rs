@@ -0,0 +1,3 @@
+pub(crate) mod watch;
+
+pub use watch::*;
@@ -0,0 +1,74 @@
+use crate::jobs::WindowSink;
+use chrono::Local;
+use livesaver_core::copify::copify_set;
+use livesaver_core::error::Error;
+use livesaver_core::history::{record_job, History, JobSettings};
+use livesaver_core::jobs::{JobKind, JobStarted, Jobs};
+use livesaver_core::utils::Reporter;
+use livesaver_core::watch::{watched_folder, ProjectWatcher, WatchSettings};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// The running folder watcher, if any, managed as Tauri state
+#[derive(Default)]
+pub struct Watcher {
+    current: Mutex<Option<ProjectWatcher>>,
+}
+
+/// Watch project folders and run copify on every set Live saves in
+/// them. Replaces a watcher that is already running.
+#[tauri::command]
+pub async fn start_watch(
+    window: tauri::Window,
+    watcher: tauri::State<'_, Watcher>,
+    settings: WatchSettings,
+) -> Result<(), Error> {
+    let folders: Vec<PathBuf> = settings.folders.iter().map(PathBuf::from).collect();
+    if folders.is_empty() {
+        return Err(Error::Generic("No folders to watch".to_string()));
+    }
+
+    // Stop the previous watcher first so a set is never queued twice
+    watcher.current.lock().unwrap().take();
+
+    let app = window.app_handle().clone();
+    let copify_settings = settings.settings;
+    let watched = folders.clone();
+    let project_watcher = ProjectWatcher::start(&folders, Duration::from_millis(settings.settle_ms), move |set| {
+        let jobs = app.state::<Jobs>();
+        let history = app.state::<History>();
+        let job = jobs.start();
+        let started_at = Local::now();
+        let _ = window.emit("job-started", JobStarted { job_id: job.id.clone(), kind: JobKind::Copify });
+
+        let mut settings = copify_settings.clone();
+        settings.folder = watched_folder(&watched, set).to_string_lossy().to_string();
+
+        let reporter = Reporter::new(&job.id, WindowSink::new(&window, "copify-progress"));
+        let result = copify_set(&job.id, &job.token, &settings, set, &reporter);
+        record_job(&history, &job.id, JobSettings::Copify(settings), started_at, &result);
+    })?;
+
+    *watcher.current.lock().unwrap() = Some(project_watcher);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_watch(watcher: tauri::State<'_, Watcher>) -> Result<(), Error> {
+    watcher.current.lock().unwrap().take();
+    Ok(())
+}
+
+/// Folders that are being watched, empty when watching is off
+#[tauri::command]
+pub async fn get_watch_status(watcher: tauri::State<'_, Watcher>) -> Result<Vec<PathBuf>, Error> {
+    Ok(watcher
+        .current
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|w| w.folders().to_vec())
+        .unwrap_or_default())
+}
@@ -176,6 +176,28 @@ const MoverForm = () => {
             </FormItem>
           )}
         />
+        <FormField
+          control={form.control}
+          name="conflict_policy"
+          render={({ field }) => (
+            <FormItem>
+              <FormLabel>When a project already exists in the target</FormLabel>
+              <FormControl>
+                <select
+                  className="flex h-9 w-full rounded-md border border-input bg-transparent px-3 py-1 text-sm shadow-sm focus-visible:outline-none focus-visible:ring-1 focus-visible:ring-ring"
+                  {...field}
+                >
+                  <option value="fail">Stop before moving anything</option>
+                  <option value="skip">Skip the project</option>
+                  <option value="rename">Add it next to the existing one, e.g. Song (2)</option>
+                  <option value="merge">Merge, keeping the newer of two files</option>
+                  <option value="overwrite">Merge, replacing existing files</option>
+                </select>
+              </FormControl>
+              <FormMessage />
+            </FormItem>
+          )}
+        />
         <h2 className="pt-6 pb-2">Export settings</h2>
         <Checker
           disabled={false}
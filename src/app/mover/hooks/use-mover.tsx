@@ -13,6 +13,8 @@ const formSchema = z.object({
   move_project_files: z.boolean().default(false),
   move_samples: z.boolean().default(false), // Default to false, cause we only want to copy if not specified to actually move the files
   create_backup: z.boolean().default(false),
+  // What happens when a project folder already exists in the target
+  conflict_policy: z.enum(['fail', 'skip', 'rename', 'merge', 'overwrite']).default('fail'),
   exclude_files: z.array(z.string()).optional().default([])
 })
 
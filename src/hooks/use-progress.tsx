@@ -5,6 +5,7 @@ import { zodResolver } from '@hookform/resolvers/zod'
 import { invoke } from '@tauri-apps/api/core'
 
 export interface IProgress {
+  job_id: string
   progress: number
   file_name: string
   is_error: boolean
@@ -12,6 +13,16 @@ export interface IProgress {
   error_msg: string
 }
 
+export interface ICommandError {
+  code: string
+  message: string
+  operation: string | null
+  paths: string[]
+  cause: string | null
+}
+
+const errorMessage = (e: any): string => (e as ICommandError)?.message ?? String(e)
+
 type ProgressContextType<T extends FieldValues> = {
   log: IProgress[]
   progress: IProgress | undefined
@@ -52,7 +63,7 @@ export const useProgress = <T extends FieldValues>(formSchema: ZodSchema<T>, com
     try {
       await invoke(command, { settings: values })
     } catch (e: any) {
-      setError(e)
+      setError(errorMessage(e))
       console.error(e)
     } finally {
       //setIsProcessing(false)
@@ -80,7 +91,7 @@ export const useProgress = <T extends FieldValues>(formSchema: ZodSchema<T>, com
       const alsFiles = ((await invoke('get_als_files', { folder: directory })) as string[]) ?? []
       setFiles(alsFiles)
     } catch (e: any) {
-      setError(e)
+      setError(errorMessage(e))
       console.log(e)
     } finally {
       setIsProjectsLoading(false)